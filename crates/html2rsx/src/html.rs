@@ -0,0 +1,322 @@
+use recrust_ast::decode_entities;
+
+// ---------------------------------- HTML Tree ----------------------------------
+
+/// A forgiving HTML parser, just good enough for the markup of mockups and snippets.
+#[derive(Clone, Debug)]
+pub enum Html {
+    Element {
+        tag: String,
+        /// Attributes without a value, like `disabled`, are `None`
+        attributes: Vec<(String, Option<String>)>,
+        children: Vec<Html>,
+        /// 1-based line of the opening tag
+        line: usize,
+    },
+    /// Text with entities already decoded
+    Text(String),
+    Comment(String),
+}
+
+/// Parses HTML into a list of top-level nodes, closing unclosed elements at the end. Problems are
+/// reported as warnings, the parser never fails.
+pub fn parse_html(html: &str, warnings: &mut Vec<String>) -> Vec<Html> {
+    let mut parser = Parser {
+        html,
+        pos: 0,
+        warnings,
+    };
+    let mut stack: Vec<Html> = Vec::new();
+    let mut roots = Vec::new();
+
+    while parser.pos < html.len() {
+        let rest = parser.rest();
+        let node = if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").unwrap_or(comment.len());
+            parser.pos += 4 + (end + 3).min(comment.len());
+            let comment = comment[..end].trim().to_string();
+            Html::Comment(comment)
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            // `<!DOCTYPE html>`, `<?xml ... ?>`
+            parser.pos += rest.find('>').map_or(rest.len(), |n| n + 1);
+            continue;
+        } else if rest.starts_with("</") {
+            let line = parser.line();
+            parser.pos += 2;
+            let tag = parser.name();
+            parser.pos += parser
+                .rest()
+                .find('>')
+                .map_or(parser.rest().len(), |n| n + 1);
+
+            match stack
+                .iter()
+                .rposition(|open| tag_of(open).eq_ignore_ascii_case(&tag))
+            {
+                Some(index) => {
+                    while stack.len() > index {
+                        close(&mut stack, &mut roots);
+                    }
+                }
+                None => parser.warnings.push(format!(
+                    "line {}: ignored stray closing tag `</{}>`",
+                    line, tag
+                )),
+            }
+            continue;
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            let line = parser.line();
+            parser.pos += 1;
+            let mut tag = parser.name();
+            if !in_foreign(&stack) {
+                tag = tag.to_ascii_lowercase();
+            }
+            let foreign = in_foreign(&stack) || matches!(tag.as_str(), "svg" | "math");
+            let (attributes, self_closing) = parser.attributes(foreign);
+
+            // Implied end tags: `<li>a<li>b`, `<p>a<p>b`, `<td>a<td>b`
+            while let Some(open) = stack.last()
+                && implicitly_closed_by(tag_of(open), &tag)
+            {
+                close(&mut stack, &mut roots);
+            }
+
+            let mut element = Html::Element {
+                tag: tag.clone(),
+                attributes,
+                children: Vec::new(),
+                line,
+            };
+            if self_closing || VOID_ELEMENTS.contains(&tag.as_str()) {
+                push(&mut stack, &mut roots, element);
+            } else if RAW_TEXT_ELEMENTS.contains(&tag.as_str()) {
+                let text = parser.raw_text(&tag);
+                if let Html::Element { children, .. } = &mut element
+                    && !text.is_empty()
+                {
+                    children.push(Html::Text(text));
+                }
+                push(&mut stack, &mut roots, element);
+            } else {
+                stack.push(element);
+            }
+            continue;
+        } else {
+            // Text up to the next tag, a lone `<` is part of the text
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let end = rest[first..].find('<').map_or(rest.len(), |n| n + first);
+            parser.pos += end;
+            Html::Text(decode_entities(&rest[..end]))
+        };
+
+        push(&mut stack, &mut roots, node);
+    }
+
+    while !stack.is_empty() {
+        close(&mut stack, &mut roots);
+    }
+    roots
+}
+
+struct Parser<'a, 'w> {
+    html: &'a str,
+    pos: usize,
+    warnings: &'w mut Vec<String>,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn rest(&self) -> &'a str {
+        &self.html[self.pos..]
+    }
+
+    fn line(&self) -> usize {
+        self.html[..self.pos].matches('\n').count() + 1
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// A tag or attribute name.
+    fn name(&mut self) -> String {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(rest.len());
+        self.pos += end;
+        rest[..end].to_string()
+    }
+
+    /// Parses the attributes up to the end of the opening tag, and whether it is self-closing.
+    fn attributes(&mut self, foreign: bool) -> (Vec<(String, Option<String>)>, bool) {
+        let line = self.line();
+        let mut attributes: Vec<(String, Option<String>)> = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.is_empty() {
+                return (attributes, false);
+            } else if rest.starts_with("/>") {
+                self.pos += 2;
+                return (attributes, true);
+            } else if rest.starts_with('>') {
+                self.pos += 1;
+                return (attributes, false);
+            } else if rest.starts_with('/') {
+                self.pos += 1;
+                continue;
+            }
+
+            let mut name = self.name();
+            if name.is_empty() {
+                // A stray `=`
+                self.pos += 1;
+                continue;
+            }
+            if !foreign {
+                name = name.to_ascii_lowercase();
+            }
+
+            self.skip_whitespace();
+            let value = if self.rest().starts_with('=') {
+                self.pos += 1;
+                self.skip_whitespace();
+                Some(decode_entities(&self.value()))
+            } else {
+                None
+            };
+
+            // Like browsers, keep the first of duplicate attributes
+            if attributes.iter().any(|(existing, _)| *existing == name) {
+                self.warnings.push(format!(
+                    "line {}: dropped duplicate attribute `{}`",
+                    line, name
+                ));
+            } else {
+                attributes.push((name, value));
+            }
+        }
+    }
+
+    fn value(&mut self) -> String {
+        let rest = self.rest();
+        match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let end = rest[1..].find(quote).map_or(rest.len(), |n| n + 1);
+                self.pos += (end + 1).min(rest.len());
+                rest[1..end].to_string()
+            }
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                self.pos += end;
+                rest[..end].to_string()
+            }
+        }
+    }
+
+    /// The content of `<script>`, `<style>`, ... up to the closing tag, which is consumed.
+    fn raw_text(&mut self, tag: &str) -> String {
+        let rest = self.rest();
+        let closing = format!("</{}", tag);
+        let end = rest
+            .to_ascii_lowercase()
+            .find(&closing)
+            .unwrap_or(rest.len());
+        self.pos += end;
+        self.pos += self.rest().find('>').map_or(self.rest().len(), |n| n + 1);
+
+        if ESCAPABLE_RAW_TEXT_ELEMENTS.contains(&tag) {
+            decode_entities(&rest[..end])
+        } else {
+            rest[..end].to_string()
+        }
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// Elements that never have children.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose content is not parsed as HTML.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+/// Raw text elements in which entities are still decoded.
+const ESCAPABLE_RAW_TEXT_ELEMENTS: &[&str] = &["textarea", "title"];
+
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "div",
+    "dl",
+    "fieldset",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+pub fn tag_of(node: &Html) -> &str {
+    match node {
+        Html::Element { tag, .. } => tag,
+        _ => "",
+    }
+}
+
+/// Whether the content of an open element is SVG or MathML, whose names are case-sensitive.
+pub fn in_foreign(stack: &[Html]) -> bool {
+    stack
+        .iter()
+        .any(|open| matches!(tag_of(open), "svg" | "math"))
+}
+
+fn implicitly_closed_by(open: &str, tag: &str) -> bool {
+    match open {
+        "p" => BLOCK_ELEMENTS.contains(&tag),
+        "li" => tag == "li",
+        "dt" | "dd" => matches!(tag, "dt" | "dd"),
+        "td" | "th" => matches!(tag, "td" | "th" | "tr"),
+        "tr" => tag == "tr",
+        "option" => matches!(tag, "option" | "optgroup"),
+        _ => false,
+    }
+}
+
+fn push(stack: &mut [Html], roots: &mut Vec<Html>, node: Html) {
+    match stack.last_mut() {
+        Some(Html::Element { children, .. }) => children.push(node),
+        _ => roots.push(node),
+    }
+}
+
+fn close(stack: &mut Vec<Html>, roots: &mut Vec<Html>) {
+    if let Some(node) = stack.pop() {
+        push(stack, roots, node);
+    }
+}
@@ -0,0 +1,247 @@
+pub mod html;
+
+use proc_macro2::Span;
+use quote::{ToTokens, quote};
+use recrust_ast::{
+    AttributeSpans, ExprNode, PartialExpr, PrintOptions, RSXAttribute, RSXAttributeName,
+    RSXAttributes, RSXElement, RSXNode, RSXTagName, collapse_whitespace, event_type, print_rsx,
+};
+use syn::{Ident, LitStr};
+
+use crate::html::{Html, parse_html};
+
+// ---------------------------------- Converter ----------------------------------
+
+#[derive(Clone, Debug)]
+pub struct Converted {
+    /// One `rsx! { ... }` block per top-level node
+    pub rsx: String,
+    /// Everything that was changed or dropped and might need a manual look
+    pub warnings: Vec<String>,
+}
+
+/// Converts an HTML document or snippet to `rsx!` source.
+///
+/// Attribute values become string literals (`id={"main"}`), attributes without a value become
+/// `{true}`, and text is quoted with whitespace collapsed the way JSX does. `<script>` and
+/// `<style>` content becomes a raw string, and SVG keeps its case-sensitive names. `class`, `for`,
+/// `data-*` and `aria-*` are kept as they are, while `className`/`htmlFor` are renamed back.
+/// What cannot be expressed, like inline `onclick` handlers, is dropped and reported in the
+/// warnings.
+pub fn html_to_rsx(html: &str, options: &PrintOptions) -> Converted {
+    let mut warnings = Vec::new();
+    let roots = parse_html(html, &mut warnings);
+
+    let mut converter = Converter { warnings };
+    let nodes: Vec<RSXNode> = roots
+        .iter()
+        .filter_map(|node| converter.node(node, false))
+        .collect();
+    if nodes.len() > 1 {
+        converter.warnings.push(format!(
+            "{} top-level nodes were converted to separate `rsx!` blocks",
+            nodes.len()
+        ));
+    }
+
+    // The body of the macro is indented once
+    let print_options = PrintOptions {
+        max_width: options
+            .max_width
+            .saturating_sub(options.indent.chars().count()),
+        ..options.clone()
+    };
+    let rsx = nodes
+        .iter()
+        .map(|node| {
+            let mut block = "rsx! {\n".to_string();
+            for line in print_rsx(node, &print_options).lines() {
+                block.push_str(&options.indent);
+                block.push_str(line);
+                block.push('\n');
+            }
+            block.push('}');
+            block
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    Converted {
+        rsx,
+        warnings: converter.warnings,
+    }
+}
+
+struct Converter {
+    warnings: Vec<String>,
+}
+
+impl Converter {
+    fn node(&mut self, node: &Html, preserve_whitespace: bool) -> Option<RSXNode> {
+        let span = Span::call_site();
+
+        match node {
+            Html::Text(text) if preserve_whitespace => Some(RSXNode::Text(LitStr::new(text, span))),
+            Html::Text(text) => {
+                let text = collapse_whitespace(text)?;
+                Some(RSXNode::Text(LitStr::new(&text, span)))
+            }
+            Html::Comment(comment) => {
+                Some(RSXNode::Comment(LitStr::new(comment, span), span.into()))
+            }
+            Html::Element {
+                tag,
+                attributes,
+                children,
+                line,
+            } => {
+                let name = tag_name(tag);
+                if name != *tag {
+                    self.warnings.push(format!(
+                        "line {}: renamed `<{}>` to `<{}>`, tags must be Rust identifiers separated by dashes",
+                        line, tag, name
+                    ));
+                }
+
+                let mut rsx_attributes = RSXAttributes(
+                    attributes
+                        .iter()
+                        .filter_map(|(name, value)| self.attribute(name, value.as_deref(), *line))
+                        .collect(),
+                );
+
+                let children: Vec<RSXNode> = if matches!(tag.as_str(), "script" | "style") {
+                    children
+                        .iter()
+                        .filter_map(|child| match child {
+                            Html::Text(text) if !text.trim().is_empty() => {
+                                Some(RSXNode::RawText(raw_string(text)))
+                            }
+                            _ => None,
+                        })
+                        .collect()
+                } else {
+                    let preserve_whitespace =
+                        preserve_whitespace || matches!(tag.as_str(), "pre" | "textarea");
+                    children
+                        .iter()
+                        .filter_map(|child| self.node(child, preserve_whitespace))
+                        .collect()
+                };
+                let children = ExprNode(
+                    children
+                        .into_iter()
+                        .map(|child| PartialExpr::RSX(Box::new(child)))
+                        .collect(),
+                );
+                rsx_attributes
+                    .push_children(children, span)
+                    .expect("`children` attributes are dropped while converting");
+
+                let mut parts = name.split('-').map(|part| Ident::new(part, span));
+                let mut tag_name = RSXTagName::from(parts.next().expect("tags are not empty"));
+                tag_name.dashed.extend(parts);
+                Some(RSXNode::RSXElement(RSXElement::new(
+                    tag_name,
+                    rsx_attributes,
+                )))
+            }
+        }
+    }
+
+    fn attribute(&mut self, name: &str, value: Option<&str>, line: usize) -> Option<RSXAttribute> {
+        let span = Span::call_site();
+        let mut warn = |message: String| self.warnings.push(format!("line {}: {}", line, message));
+
+        let name = match name {
+            // Pasted from React
+            "classname" => "class",
+            "htmlfor" => "for",
+            // Plain `href` and `lang` work for inline SVG
+            "xlink:href" => "href",
+            "xml:lang" => "lang",
+            _ if name.starts_with("xmlns:") => {
+                warn(format!(
+                    "dropped `{}`, namespace declarations are not needed for inline SVG",
+                    name
+                ));
+                return None;
+            }
+            _ if name.starts_with("on") && event_type(&name[2..]).is_some() => {
+                warn(format!(
+                    "dropped `{}`, rewrite it as `on:{}={{...}}` with a Rust closure",
+                    name,
+                    &name[2..]
+                ));
+                return None;
+            }
+            "children" => {
+                warn("dropped the `children` attribute, it is reserved for nested content".into());
+                return None;
+            }
+            _ if !is_attribute_name(name) => {
+                warn(format!(
+                    "dropped `{}`, which is not a valid RSX attribute name",
+                    name
+                ));
+                return None;
+            }
+            _ => name,
+        };
+
+        let value = match value {
+            Some(value) => LitStr::new(value, span).to_token_stream(),
+            None => quote!(true),
+        };
+        Some(RSXAttribute::Normal {
+            name: RSXAttributeName::new(name, span),
+            value: ExprNode(vec![PartialExpr::Normal(value)]),
+            spans: AttributeSpans::call_site(),
+        })
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// Turns a tag into identifiers separated by dashes: `my-widget` is kept, `my.widget` becomes
+/// `my_widget`.
+fn tag_name(tag: &str) -> String {
+    let parts: Vec<String> = tag
+        .split('-')
+        .map(|part| {
+            let part: String = part
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            match part.chars().next() {
+                Some(c) if c.is_ascii_alphabetic() => part,
+                _ => format!("x{}", part),
+            }
+        })
+        .collect();
+    parts.join("-")
+}
+
+/// Names accepted by `RSXAttributeName`: identifiers or numbers separated by dashes.
+fn is_attribute_name(name: &str) -> bool {
+    name.split('-').enumerate().all(|(index, segment)| {
+        let mut chars = segment.chars();
+        match chars.next() {
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }
+            Some(c) if c.is_ascii_digit() && index > 0 => chars.all(|c| c.is_ascii_digit()),
+            _ => false,
+        }
+    })
+}
+
+/// A raw string literal with enough `#`s for the text: `r#"a > b"#`.
+fn raw_string(text: &str) -> LitStr {
+    let mut hashes = "#".to_string();
+    while text.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    syn::parse_str(&format!("r{}\"{}\"{}", hashes, text, hashes))
+        .expect("raw strings can contain anything")
+}
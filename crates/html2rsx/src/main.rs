@@ -0,0 +1,71 @@
+use std::{io::Read, process::ExitCode};
+
+use html2rsx::html_to_rsx;
+use recrust_ast::PrintOptions;
+
+const USAGE: &str = "\
+Converts HTML to `rsx!` source
+
+Usage: html2rsx [OPTIONS] [FILE]
+
+Reads the HTML from FILE, or from standard input when it is missing or `-`, and prints the RSX.
+Anything that was changed or dropped is reported on standard error.
+
+Options:
+      --width <WIDTH>  Maximum line width [default: 100]
+      --indent <N>     Number of spaces per indentation level [default: 4]
+  -h, --help           Print help";
+
+fn main() -> ExitCode {
+    let mut options = PrintOptions::default();
+    let mut file = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--width" | "--indent" => {
+                let Some(value) = args.next().and_then(|value| value.parse::<usize>().ok()) else {
+                    eprintln!("error: `{}` expects a number\n\n{}", arg, USAGE);
+                    return ExitCode::from(2);
+                };
+                if arg == "--width" {
+                    options.max_width = value;
+                } else {
+                    options.indent = " ".repeat(value);
+                }
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            "-" => file = None,
+            _ if arg.starts_with('-') => {
+                eprintln!("error: unknown option `{}`\n\n{}", arg, USAGE);
+                return ExitCode::from(2);
+            }
+            _ => file = Some(arg),
+        }
+    }
+
+    let html = match &file {
+        Some(file) => std::fs::read_to_string(file),
+        None => {
+            let mut html = String::new();
+            std::io::stdin().read_to_string(&mut html).map(|_| html)
+        }
+    };
+    let html = match html {
+        Ok(html) => html,
+        Err(err) => {
+            eprintln!("error: {}: {}", file.as_deref().unwrap_or("<stdin>"), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let converted = html_to_rsx(&html, &options);
+    for warning in &converted.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    println!("{}", converted.rsx);
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,111 @@
+use html2rsx::html_to_rsx;
+use recrust_ast::{PrintOptions, RSXNode};
+
+fn convert(html: &str) -> (String, Vec<String>) {
+    let converted = html_to_rsx(html, &PrintOptions::default());
+
+    // Everything emitted must be accepted by `rsx!`
+    for block in converted
+        .rsx
+        .split("\n\n")
+        .filter(|block| !block.is_empty())
+    {
+        let body = block
+            .strip_prefix("rsx! {")
+            .and_then(|block| block.strip_suffix('}'))
+            .expect("an `rsx!` block");
+        if let Err(err) = syn::parse_str::<RSXNode>(body) {
+            panic!("{} in:\n{}", err, body);
+        }
+    }
+
+    (converted.rsx, converted.warnings)
+}
+
+#[test]
+fn elements_attributes_and_text() {
+    let (rsx, warnings) = convert(
+        r#"<!DOCTYPE html>
+<div class="card" id=main data-user-id="42" aria-hidden="true">
+    <label for="email">E-mail &amp; more</label>
+    <input id="email" type="email" required>
+    <p>Hello,
+       <b>world</b> &lt;3</p>
+</div>"#,
+    );
+
+    assert_eq!(
+        rsx,
+        r#"rsx! {
+    <div class={"card"} id={"main"} data-user-id={"42"} aria-hidden={"true"}>
+        <label for={"email"}>"E-mail & more"</label>
+        <input id={"email"} type={"email"} required={true} />
+        <p>
+            "Hello,"
+            <b>"world"</b>
+            " <3"
+        </p>
+    </div>
+}"#
+    );
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn inline_svg_keeps_its_case() {
+    let (rsx, warnings) = convert(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 24 24">
+  <linearGradient id="g"><stop offset="0" stop-color="#fff"/></linearGradient>
+  <use xlink:href="#icon" />
+</svg>"##,
+    );
+
+    assert_eq!(
+        rsx,
+        r##"rsx! {
+    <svg xmlns={"http://www.w3.org/2000/svg"} viewBox={"0 0 24 24"}>
+        <linearGradient id={"g"}>
+            <stop offset={"0"} stop-color={"#fff"} />
+        </linearGradient>
+        <use href={"#icon"} />
+    </svg>
+}"##
+    );
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn raw_text_and_comments() {
+    let (rsx, _) =
+        convert("<style>p > a { color: \"red\"; }</style><!-- footer --><pre>  a\n  b</pre>");
+
+    assert_eq!(
+        rsx,
+        "rsx! {\n    <style>r#\"p > a { color: \"red\"; }\"#</style>\n}\n\nrsx! {\n    <!-- \"footer\" -->\n}\n\nrsx! {\n    <pre>\"  a\\n  b\"</pre>\n}"
+    );
+}
+
+#[test]
+fn flags_what_needs_attention() {
+    let (rsx, warnings) = convert(
+        r#"<my-widget className="x" onclick="go()" @click="x" id="a" ID="b"><li>one<li>two</my-widget>"#,
+    );
+
+    assert_eq!(
+        rsx,
+        r#"rsx! {
+    <my-widget class={"x"} id={"a"}>
+        <li>"one"</li>
+        <li>"two"</li>
+    </my-widget>
+}"#
+    );
+    assert_eq!(
+        warnings,
+        [
+            "line 1: dropped duplicate attribute `id`",
+            "line 1: dropped `onclick`, rewrite it as `on:click={...}` with a Rust closure",
+            "line 1: dropped `@click`, which is not a valid RSX attribute name",
+        ]
+    );
+}
@@ -0,0 +1,393 @@
+use std::ops::Range;
+
+use recrust_ast::decode_entities;
+
+// ---------------------------------- JavaScript / JSX Tokens ----------------------------------
+
+/// A JavaScript token, with brackets grouped into trees and JSX parsed wherever an expression
+/// can start.
+#[derive(Clone, Debug)]
+pub enum Tok {
+    Ident(String),
+    Number(String),
+    /// The value of a `'...'` or `"..."` string
+    Str(String),
+    /// The literal parts of a template literal, interleaved with its `${...}` expressions
+    Template(Vec<String>, Vec<Vec<Tok>>),
+    Punct(&'static str),
+    Group {
+        open: char,
+        tokens: Vec<Tok>,
+        range: Range<usize>,
+    },
+    Comment(String),
+    Jsx(Box<Jsx>, Range<usize>),
+}
+
+#[derive(Clone, Debug)]
+pub enum Jsx {
+    Element {
+        name: String,
+        attributes: Vec<Attribute>,
+        children: Vec<Child>,
+        line: usize,
+    },
+    Fragment {
+        children: Vec<Child>,
+        line: usize,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub enum Attribute {
+    /// `name`, `name="value"`, `name={value}` or `name=<element />`
+    Named {
+        name: String,
+        value: Option<AttributeValue>,
+    },
+    /// `{...props}`
+    Spread(Vec<Tok>),
+}
+
+#[derive(Clone, Debug)]
+pub enum AttributeValue {
+    Str(String),
+    Expr(Vec<Tok>),
+    Jsx(Jsx),
+}
+
+#[derive(Clone, Debug)]
+pub enum Child {
+    /// Text as written, entities and whitespace still need to be handled
+    Text(String),
+    /// `{expr}`, with the line it starts on
+    Expr(Vec<Tok>, usize),
+    /// `{/* ... */}`
+    Comment(Vec<String>),
+    Jsx(Jsx),
+}
+
+/// Tokenizes a JavaScript or TypeScript file, parsing the JSX in it.
+pub fn parse_source(source: &str) -> Result<Vec<Tok>, String> {
+    let mut parser = Parser { source, pos: 0 };
+    parser.tokens(None)
+}
+
+const PUNCTS: &[&str] = &[
+    "===", "!==", "...", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=", "=>", "==", "!=", "<=",
+    ">=", "&&", "||", "??", "?.", "++", "--", "+=", "-=", "*=", "/=", "%=", "**", "<<", ">>", "+",
+    "-", "*", "/", "%", "=", "<", ">", "!", "~", "?", ":", ";", ",", ".", "&", "|", "^", "@", "#",
+];
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn line(&self) -> usize {
+        self.source[..self.pos].matches('\n').count() + 1
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("line {}: {}", self.line(), message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let rest = self.rest();
+        let end = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += end;
+        rest[..end].to_string()
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), String> {
+        self.skip_whitespace();
+        if !self.rest().starts_with(text) {
+            return Err(self.error(&format!("expected `{}`", text)));
+        }
+        self.pos += text.len();
+        Ok(())
+    }
+
+    /// Tokens up to the closing bracket, which is consumed, or the end of the input.
+    fn tokens(&mut self, close: Option<char>) -> Result<Vec<Tok>, String> {
+        let mut tokens = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+            let Some(c) = self.peek() else {
+                return match close {
+                    Some(close) => Err(self.error(&format!("unclosed `{}`", close))),
+                    None => Ok(tokens),
+                };
+            };
+            let start = self.pos;
+
+            let token = match c {
+                _ if Some(c) == close => {
+                    self.pos += 1;
+                    return Ok(tokens);
+                }
+                ')' | ']' | '}' => return Err(self.error(&format!("unexpected `{}`", c))),
+                '(' | '[' | '{' => {
+                    self.pos += 1;
+                    let close = match c {
+                        '(' => ')',
+                        '[' => ']',
+                        _ => '}',
+                    };
+                    let inner = self.tokens(Some(close))?;
+                    Tok::Group {
+                        open: c,
+                        tokens: inner,
+                        range: start..self.pos,
+                    }
+                }
+                _ if self.rest().starts_with("//") => {
+                    let comment = self.take_while(|c| c != '\n');
+                    Tok::Comment(comment[2..].trim().to_string())
+                }
+                _ if self.rest().starts_with("/*") => {
+                    let end = self.rest().find("*/").map_or(self.rest().len(), |n| n + 2);
+                    let comment = self.rest()[2..end.max(2)].trim_end_matches("*/").trim();
+                    let comment = comment.to_string();
+                    self.pos += end;
+                    Tok::Comment(comment)
+                }
+                '"' | '\'' => Tok::Str(self.string(c)?),
+                '`' => self.template()?,
+                '<' if jsx_allowed(tokens.last())
+                    && self.rest()[1..].starts_with(|c: char| c.is_alphabetic() || c == '>') =>
+                {
+                    let jsx = self.jsx()?;
+                    Tok::Jsx(Box::new(jsx), start..self.pos)
+                }
+                _ if c.is_ascii_digit() => Tok::Number(
+                    self.take_while(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_'),
+                ),
+                _ if c.is_alphabetic() || c == '_' || c == '$' => {
+                    Tok::Ident(self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '$'))
+                }
+                _ => {
+                    let Some(punct) = PUNCTS.iter().find(|p| self.rest().starts_with(**p)) else {
+                        return Err(self.error(&format!("unexpected character `{}`", c)));
+                    };
+                    self.pos += punct.len();
+                    Tok::Punct(punct)
+                }
+            };
+            tokens.push(token);
+        }
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, String> {
+        self.pos += 1;
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                _ if c == quote => {
+                    self.pos += i + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    Some('0') => value.push('\0'),
+                    Some('\n') => {}
+                    Some(c) => value.push(c),
+                    None => break,
+                },
+                '\n' => break,
+                _ => value.push(c),
+            }
+        }
+
+        Err(self.error("unterminated string"))
+    }
+
+    fn template(&mut self) -> Result<Tok, String> {
+        self.pos += 1;
+        let mut strings = vec![String::new()];
+        let mut exprs = Vec::new();
+
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("unterminated template literal"));
+            };
+            if c == '`' {
+                self.pos += 1;
+                return Ok(Tok::Template(strings, exprs));
+            } else if self.rest().starts_with("${") {
+                self.pos += 2;
+                exprs.push(self.tokens(Some('}'))?);
+                strings.push(String::new());
+            } else if c == '\\' {
+                self.pos += 1;
+                if let Some(c) = self.peek() {
+                    strings.last_mut().unwrap().push(c);
+                    self.pos += c.len_utf8();
+                }
+            } else {
+                strings.last_mut().unwrap().push(c);
+                self.pos += c.len_utf8();
+            }
+        }
+    }
+
+    /// Parses an element or fragment, starting at its `<`.
+    fn jsx(&mut self) -> Result<Jsx, String> {
+        let line = self.line();
+        self.pos += 1;
+        self.skip_whitespace();
+
+        // Fragment: `<>...</>`
+        if self.peek() == Some('>') {
+            self.pos += 1;
+            let children = self.children("")?;
+            return Ok(Jsx::Fragment { children, line });
+        }
+
+        let name = self.name();
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(Jsx::Element {
+                    name,
+                    attributes,
+                    children: Vec::new(),
+                    line,
+                });
+            } else if rest.starts_with('>') {
+                self.pos += 1;
+                let children = self.children(&name)?;
+                return Ok(Jsx::Element {
+                    name,
+                    attributes,
+                    children,
+                    line,
+                });
+            } else if rest.starts_with('{') {
+                self.pos += 1;
+                self.expect("...")?;
+                attributes.push(Attribute::Spread(self.tokens(Some('}'))?));
+                continue;
+            } else if rest.is_empty() {
+                return Err(self.error(&format!("unclosed `<{}>`", name)));
+            }
+
+            let attribute = self.name();
+            if attribute.is_empty() {
+                return Err(self.error(&format!("unexpected character in `<{}>`", name)));
+            }
+            self.skip_whitespace();
+            let value = if self.rest().starts_with('=') {
+                self.pos += 1;
+                self.skip_whitespace();
+                Some(match self.peek() {
+                    Some(quote @ ('"' | '\'')) => {
+                        // JSX strings have no escapes, only entities
+                        self.pos += 1;
+                        let value = self.take_while(|c| c != quote);
+                        self.pos += 1;
+                        AttributeValue::Str(decode_entities(&value))
+                    }
+                    Some('{') => {
+                        self.pos += 1;
+                        AttributeValue::Expr(self.tokens(Some('}'))?)
+                    }
+                    Some('<') => AttributeValue::Jsx(self.jsx()?),
+                    _ => return Err(self.error(&format!("expected a value for `{}`", attribute))),
+                })
+            } else {
+                None
+            };
+            attributes.push(Attribute::Named {
+                name: attribute,
+                value,
+            });
+        }
+    }
+
+    /// A tag or attribute name: `div`, `my-element`, `Foo.Bar`, `aria-label`, `xlink:href`.
+    fn name(&mut self) -> String {
+        self.take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | '-' | '.' | ':'))
+    }
+
+    /// Children up to and including the closing tag.
+    fn children(&mut self, name: &str) -> Result<Vec<Child>, String> {
+        let mut children = Vec::new();
+
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(self.error(&format!("unclosed `<{}>`", name)));
+            } else if rest.starts_with("</") {
+                self.pos += 2;
+                let closing = {
+                    self.skip_whitespace();
+                    self.name()
+                };
+                if closing != name {
+                    return Err(
+                        self.error(&format!("expected `</{}>`, found `</{}>`", name, closing))
+                    );
+                }
+                self.expect(">")?;
+                return Ok(children);
+            } else if rest.starts_with('<') {
+                children.push(Child::Jsx(self.jsx()?));
+            } else if rest.starts_with('{') {
+                let line = self.line();
+                self.pos += 1;
+                let tokens = self.tokens(Some('}'))?;
+                if tokens.iter().all(|token| matches!(token, Tok::Comment(_))) {
+                    let comments = tokens
+                        .into_iter()
+                        .filter_map(|token| match token {
+                            Tok::Comment(comment) => Some(comment),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
+                    if !comments.is_empty() {
+                        children.push(Child::Comment(comments));
+                    }
+                } else {
+                    children.push(Child::Expr(tokens, line));
+                }
+            } else {
+                children.push(Child::Text(self.take_while(|c| c != '<' && c != '{')));
+            }
+        }
+    }
+}
+
+/// Whether a `<` after this token starts JSX rather than being a comparison.
+pub(crate) fn jsx_allowed(previous: Option<&Tok>) -> bool {
+    match previous {
+        None | Some(Tok::Punct(_) | Tok::Comment(_)) => true,
+        Some(Tok::Ident(ident)) => matches!(
+            ident.as_str(),
+            "return" | "yield" | "await" | "default" | "case" | "in" | "of" | "typeof"
+        ),
+        _ => false,
+    }
+}
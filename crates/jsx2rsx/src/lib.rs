@@ -0,0 +1,657 @@
+pub mod jsx;
+
+use recrust_ast::{FormatOptions, collapse_whitespace, decode_entities, event_type, format_source};
+
+use crate::jsx::{Attribute, AttributeValue, Child, Jsx, Tok, jsx_allowed, parse_source};
+
+// ---------------------------------- Converter ----------------------------------
+
+#[derive(Clone, Debug)]
+pub struct Converted {
+    pub output: String,
+    /// Constructs that were converted on a best-effort basis, or not at all
+    pub warnings: Vec<String>,
+}
+
+/// Replaces every JSX expression in a JavaScript/TypeScript source with an `rsx!` block.
+///
+/// The surrounding JavaScript is left as it is. Inside the JSX, `className`/`htmlFor` and event
+/// props are renamed, `{...props}` becomes `{..props}`, `cond && <x />` becomes
+/// `cond.then(|| <x />)`, ternaries become `if`/`else`, arrow functions become closures (taking
+/// `_` for handlers without parameters) and `.map(...)` over arrays becomes
+/// `.iter().map(...).collect::<Vec<_>>()`, with `.enumerate()` when the callback takes the index.
+/// Template literals become `format!`, `style={{ ... }}` objects become `style:` directives and
+/// `{/* ... */}` comments become Rust comments. What is converted on a best-effort basis
+/// (fragments, `null`, `?.`, refs, ...) is reported in the warnings, with its line.
+pub fn jsx_to_rsx(source: &str, options: &FormatOptions) -> Result<Converted, String> {
+    let tokens = parse_source(source)?;
+
+    let mut roots = Vec::new();
+    find_roots(&tokens, &mut roots);
+
+    let mut converter = Converter {
+        warnings: Vec::new(),
+        line: 1,
+    };
+    let mut output = source.to_string();
+    // Replace from the end so the ranges of earlier roots stay valid
+    for (jsx, range) in roots.into_iter().rev() {
+        let line_start = source[..range.start].rfind('\n').map_or(0, |i| i + 1);
+        let indent: String = source[line_start..]
+            .chars()
+            .take_while(|c| c.is_whitespace() && *c != '\n')
+            .collect();
+
+        let rsx = converter.root(jsx, options, indent.chars().count());
+        output.replace_range(range, &rsx.replace('\n', &format!("\n{}", indent)));
+    }
+
+    Ok(Converted {
+        output,
+        warnings: converter.warnings,
+    })
+}
+
+/// Finds the outermost JSX expressions, along with the range to replace. JSX wrapped in
+/// parentheses, like `return (<div />)`, replaces the parentheses too.
+fn find_roots<'a>(tokens: &'a [Tok], roots: &mut Vec<(&'a Jsx, std::ops::Range<usize>)>) {
+    for (index, token) in tokens.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|index| &tokens[index]);
+        match token {
+            Tok::Jsx(jsx, range) => roots.push((jsx, range.clone())),
+            Tok::Group {
+                open: '(',
+                tokens: inner,
+                range,
+            } if matches!(inner[..], [Tok::Jsx(..)]) && jsx_allowed(previous) => {
+                if let [Tok::Jsx(jsx, _)] = &inner[..] {
+                    roots.push((jsx, range.clone()));
+                }
+            }
+            Tok::Group { tokens: inner, .. } => find_roots(inner, roots),
+            Tok::Template(_, exprs) => exprs.iter().for_each(|expr| find_roots(expr, roots)),
+            _ => {}
+        }
+    }
+    roots.sort_by_key(|(_, range)| range.start);
+}
+
+struct Converter {
+    warnings: Vec<String>,
+    /// Line of the element being converted, for warnings
+    line: usize,
+}
+
+impl Converter {
+    fn warn(&mut self, message: impl Into<String>) {
+        let warning = format!("line {}: {}", self.line, message.into());
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Converts a top-level JSX expression to a formatted `rsx!` block.
+    fn root(&mut self, jsx: &Jsx, options: &FormatOptions, indent: usize) -> String {
+        let options = FormatOptions {
+            max_width: options.max_width.saturating_sub(indent),
+            ..options.clone()
+        };
+
+        let blocks: Vec<String> = match jsx {
+            Jsx::Fragment { children, line } => {
+                let children = self.children(children, false);
+                self.line = *line;
+                if children.len() > 1 {
+                    self.warn(
+                        "fragments are not supported, the children were converted to a `Vec` of `rsx!` blocks",
+                    );
+                }
+                children
+            }
+            Jsx::Element { .. } => vec![self.jsx(jsx, false)],
+        };
+
+        let blocks: Vec<String> = blocks
+            .into_iter()
+            .map(|block| {
+                let block = format!("rsx! {{\n{}\n}}", block);
+                let mut formatted = format_source(&block, &options);
+                // Expressions are never split, so a block too wide for any layout is formatted
+                // without a width instead
+                if !formatted.diagnostics.is_empty() {
+                    let options = FormatOptions {
+                        max_width: usize::MAX,
+                        ..options.clone()
+                    };
+                    formatted = format_source(&block, &options);
+                }
+                if let Some(diagnostic) = formatted.diagnostics.first() {
+                    self.warn(format!(
+                        "the converted RSX could not be formatted: {}",
+                        diagnostic.message
+                    ));
+                }
+                formatted.output
+            })
+            .collect();
+
+        match &blocks[..] {
+            [block] => block.clone(),
+            _ => format!("vec![{}]", blocks.join(", ")),
+        }
+    }
+
+    /// Converts an element, or the children of a fragment as a `vec![...]`.
+    fn jsx(&mut self, jsx: &Jsx, in_svg: bool) -> String {
+        let (name, attributes, children, line) = match jsx {
+            Jsx::Element {
+                name,
+                attributes,
+                children,
+                line,
+            } => (name, attributes, children, line),
+            Jsx::Fragment { children, line } => {
+                self.line = *line;
+                self.warn("fragments are not supported, the children were converted to a `Vec`");
+                return format!("vec![{}]", self.children(children, in_svg).join(", "));
+            }
+        };
+        self.line = *line;
+
+        // Member expressions become paths, which are components: `<Foo.Bar>` is `<Foo::Bar>`
+        let component = name.starts_with(|c: char| c.is_uppercase()) || name.contains('.');
+        let in_svg = in_svg || name == "svg";
+        let tag: String = name
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if tag != *name {
+            self.warn(format!(
+                "renamed `<{}>` to `<{}>`, tags must be Rust identifiers",
+                name, tag
+            ));
+        }
+        let tag = tag.replace('.', "::");
+
+        let mut out = format!("<{}", tag);
+        for attribute in attributes {
+            if let Some(attribute) = self.attribute(attribute, component, in_svg) {
+                out.push(' ');
+                out.push_str(&attribute);
+            }
+        }
+
+        let children = self.children(children, in_svg);
+        if children.is_empty() {
+            out.push_str(" />");
+            return out;
+        }
+        // On one line, so that the formatter doesn't keep elements nested in expressions as
+        // written; comments end with their own line break
+        out.push('>');
+        for child in children {
+            out.push(' ');
+            out.push_str(&child);
+        }
+        out.push_str(&format!(" </{}>", tag));
+        out
+    }
+
+    /// Converts children, attaching `{/* ... */}` comments to the child that follows them.
+    fn children(&mut self, children: &[Child], in_svg: bool) -> Vec<String> {
+        let mut out = Vec::new();
+        let mut comments = String::new();
+
+        for child in children {
+            let child = match child {
+                Child::Text(text) => match collapse_whitespace(text) {
+                    Some(text) => format!("{:?}", decode_entities(&text)),
+                    None => continue,
+                },
+                Child::Comment(lines) => {
+                    for line in lines.iter().flat_map(|comment| comment.lines()) {
+                        comments.push_str(&format!("// {}\n", line.trim()));
+                    }
+                    continue;
+                }
+                // Fragments nested in elements are flattened into their parent
+                Child::Jsx(Jsx::Fragment { children, .. }) => {
+                    out.extend(self.children(children, in_svg));
+                    continue;
+                }
+                Child::Jsx(jsx) => self.jsx(jsx, in_svg),
+                Child::Expr(tokens, line) => {
+                    self.line = *line;
+                    format!("{{{}}}", self.expr(tokens))
+                }
+            };
+            out.push(format!("{}{}", std::mem::take(&mut comments), child));
+        }
+
+        // Only comments in front of a node are kept by the formatter
+        if !comments.is_empty() {
+            self.warn(format!(
+                "dropped a trailing comment: `{}`",
+                comments.trim_end()
+            ));
+        }
+        out
+    }
+
+    fn attribute(
+        &mut self,
+        attribute: &Attribute,
+        component: bool,
+        in_svg: bool,
+    ) -> Option<String> {
+        let (name, value) = match attribute {
+            Attribute::Spread(tokens) => {
+                return match &tokens[..] {
+                    [Tok::Ident(ident)] => Some(format!("{{..{}}}", ident)),
+                    _ => {
+                        let spread = self.expr(tokens);
+                        self.warn(format!(
+                            "dropped the spread of `{}`, only identifiers can be spread; bind it to a variable first",
+                            spread
+                        ));
+                        None
+                    }
+                };
+            }
+            Attribute::Named { name, value } => (name.as_str(), value.as_ref()),
+        };
+
+        // `style={{ fontSize: 12 }}` becomes `style:font-size={"12px"}`
+        if name == "style"
+            && !component
+            && let Some(AttributeValue::Expr(tokens)) = value
+            && let Some(styles) = self.style_directives(tokens)
+        {
+            return Some(styles);
+        }
+
+        // `dangerouslySetInnerHTML={{ __html: html }}` becomes `inner_html={Trusted::new(html)}`
+        if name == "dangerouslySetInnerHTML"
+            && let Some(AttributeValue::Expr(tokens)) = value
+            && let [
+                Tok::Group {
+                    open: '{', tokens, ..
+                },
+            ] = &tokens[..]
+            && let [Tok::Ident(key), Tok::Punct(":"), html @ ..] = &tokens[..]
+            && key == "__html"
+        {
+            self.warn(
+                "`dangerouslySetInnerHTML` became `inner_html`, make sure the HTML is trusted",
+            );
+            let html = self.expr(html);
+            return Some(format!("inner_html={{Trusted::new({})}}", html));
+        }
+
+        let name = self.attribute_name(name, component, in_svg)?;
+
+        // Handlers without parameters still receive the event: `() => go()` is `move |_| go()`
+        if name.starts_with("on:")
+            && let Some(AttributeValue::Expr(tokens)) = value
+            && let [
+                Tok::Group {
+                    open: '(',
+                    tokens: params,
+                    ..
+                },
+                Tok::Punct("=>"),
+                body @ ..,
+            ] = &tokens[..]
+            && params.is_empty()
+        {
+            let body = self.expr(body);
+            return Some(format!("{}={{move |_| {}}}", name, body));
+        }
+
+        let value = match value {
+            None => "true".to_string(),
+            Some(AttributeValue::Str(value)) => format!("{:?}", value),
+            Some(AttributeValue::Expr(tokens)) => self.expr(tokens),
+            Some(AttributeValue::Jsx(jsx)) => self.jsx(jsx, in_svg),
+        };
+        Some(format!("{}={{{}}}", name, value))
+    }
+
+    fn attribute_name(&mut self, name: &str, component: bool, in_svg: bool) -> Option<String> {
+        if component {
+            return Some(name.to_string());
+        }
+
+        let renamed = match name {
+            "className" => "class".to_string(),
+            "htmlFor" => "for".to_string(),
+            "xlinkHref" => "href".to_string(),
+            "ref" => {
+                self.warn("dropped `ref`, refs need manual attention");
+                return None;
+            }
+            _ if name.starts_with("on") && name[2..].starts_with(|c: char| c.is_uppercase()) => {
+                let event = match name[2..].to_lowercase().as_str() {
+                    "doubleclick" => "dblclick".to_string(),
+                    event => event.to_string(),
+                };
+                if event_type(&event).is_none() {
+                    self.warn(format!(
+                        "dropped `{}`, `{}` is not a known DOM event",
+                        name, event
+                    ));
+                    return None;
+                }
+                format!("on:{}", event)
+            }
+            _ if name.contains(['-', ':']) => name.to_string(),
+            _ if in_svg && !SVG_CAMEL_CASE_ATTRIBUTES.contains(&name) => kebab_case(name),
+            _ if in_svg => name.to_string(),
+            _ => name.to_lowercase(),
+        };
+
+        if renamed.contains(':') && !renamed.starts_with("on:") {
+            self.warn(format!(
+                "dropped `{}`, which is not a valid RSX attribute name",
+                name
+            ));
+            return None;
+        }
+        Some(renamed)
+    }
+
+    /// Converts a simple style object to `style:property` directives.
+    fn style_directives(&mut self, tokens: &[Tok]) -> Option<String> {
+        let [
+            Tok::Group {
+                open: '{', tokens, ..
+            },
+        ] = tokens
+        else {
+            return None;
+        };
+
+        let mut directives = Vec::new();
+        for entry in tokens.split(|token| matches!(token, Tok::Punct(","))) {
+            let (key, value) = match entry {
+                [] => continue,
+                [Tok::Ident(key) | Tok::Str(key), Tok::Punct(":"), value @ ..]
+                    if !value.is_empty() =>
+                {
+                    (key, value)
+                }
+                _ => {
+                    self.warn("kept a `style` object that is not a plain list of properties");
+                    return None;
+                }
+            };
+
+            let property = kebab_case(key);
+            let value = match value {
+                // React adds `px` to numbers, except for unitless properties
+                [Tok::Number(number)] if !UNITLESS_PROPERTIES.contains(&key.as_str()) => {
+                    format!("\"{}px\"", number)
+                }
+                [Tok::Number(number)] => format!("\"{}\"", number),
+                _ => self.expr(value),
+            };
+            directives.push(format!("style:{}={{{}}}", property, value));
+        }
+        Some(directives.join(" "))
+    }
+
+    /// Converts a JavaScript expression to Rust on a best-effort basis.
+    fn expr(&mut self, tokens: &[Tok]) -> String {
+        let tokens: Vec<&Tok> = tokens
+            .iter()
+            .filter(|token| match token {
+                Tok::Comment(comment) => {
+                    self.warn(format!(
+                        "dropped a comment inside an expression: `{}`",
+                        comment
+                    ));
+                    false
+                }
+                _ => true,
+            })
+            .collect();
+
+        // `cond ? a : b`
+        if let Some(question) = tokens.iter().position(|t| matches!(t, Tok::Punct("?"))) {
+            let mut depth = 0;
+            for (index, token) in tokens.iter().enumerate().skip(question + 1) {
+                match token {
+                    Tok::Punct("?") => depth += 1,
+                    Tok::Punct(":") if depth > 0 => depth -= 1,
+                    Tok::Punct(":") => {
+                        let condition = self.expr_refs(&tokens[..question]);
+                        let then = self.expr_refs(&tokens[question + 1..index]);
+                        let otherwise = self.expr_refs(&tokens[index + 1..]);
+                        return format!("if {} {{ {} }} else {{ {} }}", condition, then, otherwise);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // `cond && <x />`
+        if let [condition @ .., Tok::Punct("&&"), Tok::Jsx(jsx, _)] = &tokens[..]
+            && !condition.is_empty()
+        {
+            let condition = match self.expr_refs(condition) {
+                condition if condition.contains(' ') => format!("({})", condition),
+                condition => condition,
+            };
+            self.warn("`cond && <x />` became `cond.then(|| <x />)`, check that the condition is a `bool`");
+            let jsx = self.jsx(jsx, false);
+            return format!("{}.then(|| {})", condition, jsx);
+        }
+
+        self.expr_refs(&tokens)
+    }
+
+    fn expr_refs(&mut self, tokens: &[&Tok]) -> String {
+        let mut out: Vec<String> = Vec::new();
+        let mut index = 0;
+
+        while index < tokens.len() {
+            let next = tokens.get(index + 1);
+            let token = tokens[index];
+            index += 1;
+
+            match token {
+                // `x => ...` and `(a, b) => ...`
+                Tok::Ident(param) if matches!(next, Some(Tok::Punct("=>"))) => {
+                    out.push(format!("move |{}|", param));
+                    index += 1;
+                }
+                Tok::Group {
+                    open: '(',
+                    tokens: params,
+                    ..
+                } if matches!(next, Some(Tok::Punct("=>"))) => {
+                    if params
+                        .iter()
+                        .any(|param| matches!(param, Tok::Group { .. } | Tok::Punct(":")))
+                    {
+                        self.warn("arrow function parameters with destructuring or types need manual attention");
+                    }
+                    let params = self.expr(params);
+                    out.push(format!("move |{}|", params));
+                    index += 1;
+                    if let Some(Tok::Group { open: '{', .. }) = tokens.get(index) {
+                        self.warn("arrow function with a block body needs manual attention");
+                    }
+                }
+                Tok::Ident(ident) if ident == "async" => {}
+                Tok::Ident(ident) => out.push(match ident.as_str() {
+                    "null" | "undefined" => {
+                        self.warn(format!("`{}` became `None`", ident));
+                        "None".to_string()
+                    }
+                    "this" => {
+                        self.warn("`this` needs manual attention");
+                        ident.clone()
+                    }
+                    _ => ident.clone(),
+                }),
+                // `items.map(f)`
+                Tok::Punct(".")
+                    if matches!(next, Some(Tok::Ident(method)) if method == "map")
+                        && matches!(tokens.get(index + 1), Some(Tok::Group { open: '(', .. })) =>
+                {
+                    let Some(Tok::Group { tokens: args, .. }) = tokens.get(index + 1) else {
+                        unreachable!()
+                    };
+                    index += 2;
+
+                    // `(item, i) => ...` takes the index as well
+                    if let [
+                        Tok::Group {
+                            open: '(',
+                            tokens: params,
+                            ..
+                        },
+                        Tok::Punct("=>"),
+                        body @ ..,
+                    ] = &args[..]
+                        && let [
+                            Tok::Ident(item),
+                            Tok::Punct(","),
+                            Tok::Ident(index),
+                            rest @ ..,
+                        ] = &params[..]
+                    {
+                        if !rest.is_empty() {
+                            self.warn("the array parameter of a `.map(...)` callback needs manual attention");
+                        }
+                        let body = self.expr(body);
+                        out.push(format!(
+                            ".iter().enumerate().map(move |({}, {})| {}).collect::<Vec<_>>()",
+                            index, item, body
+                        ));
+                        continue;
+                    }
+
+                    let args = self.expr(args);
+                    out.push(format!(".iter().map({}).collect::<Vec<_>>()", args));
+                }
+                Tok::Punct(".") if matches!(next, Some(Tok::Ident(method)) if ARRAY_METHODS.contains(&method.as_str())) =>
+                {
+                    if let Some(Tok::Ident(method)) = next {
+                        self.warn(format!("`.{}(...)` needs manual attention", method));
+                    }
+                    out.push(".".to_string());
+                }
+                Tok::Punct(punct) => out.push(match *punct {
+                    "===" => "==".to_string(),
+                    "!==" => "!=".to_string(),
+                    "?." | "??" | "..." => {
+                        self.warn(format!("`{}` needs manual attention", punct));
+                        punct.to_string()
+                    }
+                    _ => punct.to_string(),
+                }),
+                Tok::Number(number) => out.push(number.clone()),
+                Tok::Str(value) => out.push(format!("{:?}", value)),
+                Tok::Template(strings, exprs) => {
+                    let format = strings
+                        .iter()
+                        .map(|s| s.replace('{', "{{").replace('}', "}}"))
+                        .collect::<Vec<_>>()
+                        .join("{}");
+                    let args: String = exprs
+                        .iter()
+                        .map(|expr| format!(", {}", self.expr(expr)))
+                        .collect();
+                    out.push(format!("format!({:?}{})", format, args));
+                }
+                Tok::Group { open, tokens, .. } => {
+                    let close = match open {
+                        '(' => ')',
+                        '[' => ']',
+                        _ => '}',
+                    };
+                    let inner = self.expr(tokens);
+                    out.push(format!("{}{}{}", open, inner, close));
+                }
+                Tok::Jsx(jsx, _) => out.push(self.jsx(jsx, false)),
+                Tok::Comment(_) => {}
+            }
+        }
+
+        out.join(" ")
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// Array methods that have no direct equivalent on Rust slices or iterators.
+const ARRAY_METHODS: &[&str] = &[
+    "filter", "forEach", "reduce", "find", "some", "every", "flatMap", "join",
+];
+
+/// SVG attributes that are camelCase in the markup as well.
+const SVG_CAMEL_CASE_ATTRIBUTES: &[&str] = &[
+    "viewBox",
+    "preserveAspectRatio",
+    "gradientUnits",
+    "gradientTransform",
+    "patternUnits",
+    "patternTransform",
+    "clipPathUnits",
+    "markerWidth",
+    "markerHeight",
+    "markerUnits",
+    "refX",
+    "refY",
+    "stdDeviation",
+    "textLength",
+    "pathLength",
+    "spreadMethod",
+    "maskUnits",
+    "filterUnits",
+    "baseFrequency",
+    "numOctaves",
+    "tableValues",
+    "kernelMatrix",
+];
+
+/// CSS properties to which React does not add `px`.
+const UNITLESS_PROPERTIES: &[&str] = &[
+    "opacity",
+    "zIndex",
+    "flex",
+    "flexGrow",
+    "flexShrink",
+    "fontWeight",
+    "lineHeight",
+    "order",
+    "zoom",
+    "orphans",
+    "widows",
+    "columnCount",
+    "gridRow",
+    "gridColumn",
+    "tabSize",
+];
+
+/// `fontSize` becomes `font-size`.
+fn kebab_case(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() {
+            out.push('-');
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
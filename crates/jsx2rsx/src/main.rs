@@ -0,0 +1,80 @@
+use std::{io::Read, process::ExitCode};
+
+use jsx2rsx::jsx_to_rsx;
+use recrust_ast::FormatOptions;
+
+const USAGE: &str = "\
+Converts the JSX in a JavaScript/TypeScript file to `rsx!` blocks
+
+Usage: jsx2rsx [OPTIONS] [FILE]
+
+Reads FILE, or standard input when it is missing or `-`, and prints it with every JSX expression
+replaced by an `rsx!` block. The surrounding code is left as it is. Constructs that need manual
+attention are reported on standard error.
+
+Options:
+      --width <WIDTH>  Maximum line width [default: 100]
+      --indent <N>     Number of spaces per indentation level [default: 4]
+  -h, --help           Print help";
+
+fn main() -> ExitCode {
+    let mut options = FormatOptions::default();
+    let mut file = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--width" | "--indent" => {
+                let Some(value) = args.next().and_then(|value| value.parse::<usize>().ok()) else {
+                    eprintln!("error: `{}` expects a number\n\n{}", arg, USAGE);
+                    return ExitCode::from(2);
+                };
+                if arg == "--width" {
+                    options.max_width = value;
+                } else {
+                    options.indent = " ".repeat(value);
+                }
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            "-" => file = None,
+            _ if arg.starts_with('-') => {
+                eprintln!("error: unknown option `{}`\n\n{}", arg, USAGE);
+                return ExitCode::from(2);
+            }
+            _ => file = Some(arg),
+        }
+    }
+
+    let source = match &file {
+        Some(file) => std::fs::read_to_string(file),
+        None => {
+            let mut source = String::new();
+            std::io::stdin().read_to_string(&mut source).map(|_| source)
+        }
+    };
+    let name = file.as_deref().unwrap_or("<stdin>");
+    let source = match source {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("error: {}: {}", name, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match jsx_to_rsx(&source, &options) {
+        Ok(converted) => {
+            for warning in &converted.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            print!("{}", converted.output);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {}: {}", name, err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use jsx2rsx::jsx_to_rsx;
+use recrust_ast::FormatOptions;
+
+fn convert(source: &str) -> (String, Vec<String>) {
+    let converted = jsx_to_rsx(source, &FormatOptions::default()).expect("valid JSX");
+    (converted.output, converted.warnings)
+}
+
+#[test]
+fn component_body() {
+    let (output, warnings) = convert(
+        r#"function Card({ title, user }) {
+  return (
+    <div className="card" data-id={user.id}>
+      {/* Header */}
+      <h2 className={active ? "title active" : 'title'}>{title}</h2>
+      <label htmlFor="name">Name</label>
+      <input id="name" onChange={(e) => setName(e)} readOnly />
+    </div>
+  );
+}"#,
+    );
+
+    assert_eq!(
+        output,
+        r#"function Card({ title, user }) {
+  return rsx! {
+      <div class={"card"} data-id={user.id}>
+          // Header
+          <h2 class={if active { "title active" } else { "title" }}>{title}</h2>
+          <label for={"name"}>"Name"</label>
+          <input id={"name"} on:change={move |e| setName(e)} readonly={true} />
+      </div>
+  };
+}"#
+    );
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn spreads_lists_and_conditionals() {
+    let (output, warnings) = convert(
+        r##"const list = <ul {...props}>{items.map(item => <li key={item.id}>{`#${item.id}`}</li>)}{loading && <Spinner />}</ul>;"##,
+    );
+
+    assert_eq!(
+        output,
+        r##"const list = rsx! {
+    <ul {..props}>
+        {items.iter().map(move |item| <li key={item.id}>{format!("#{}", item.id)}</li>).collect::<Vec<_>>()}
+        {loading.then(|| <Spinner />)}
+    </ul>
+};"##
+    );
+    assert_eq!(
+        warnings,
+        [
+            "line 1: `cond && <x />` became `cond.then(|| <x />)`, check that the condition is a `bool`"
+        ]
+    );
+}
+
+#[test]
+fn indexed_maps_and_handlers_without_parameters() {
+    let (output, warnings) = convert(
+        r#"x = <ol>{items.map((item, i) => <li key={i} onClick={() => select(i)}>{item}</li>)}</ol>"#,
+    );
+
+    assert_eq!(
+        output,
+        r#"x = rsx! { <ol>{items.iter().enumerate().map(move |(i, item)| <li key={i} on:click={move |_| select(i)}>{item}</li>).collect::<Vec<_>>()}</ol> }"#
+    );
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    let (_, warnings) = convert("x = <ul>{items.map((item, i, all) => <li>{all.len}</li>)}</ul>");
+    assert_eq!(
+        warnings,
+        ["line 1: the array parameter of a `.map(...)` callback needs manual attention"]
+    );
+}
+
+#[test]
+fn styles_svg_and_inner_html() {
+    let (output, _) = convert(
+        r#"x = <div style={{ fontSize: 12, zIndex: 2, color: theme.color }}><svg viewBox="0 0 1 1"><path strokeWidth="2" /></svg><article dangerouslySetInnerHTML={{ __html: html }} /></div>"#,
+    );
+
+    assert_eq!(
+        output,
+        r#"x = rsx! {
+    <div style:font-size={"12px"} style:z-index={"2"} style:color={theme.color}>
+        <svg viewBox={"0 0 1 1"}>
+            <path stroke-width={"2"} />
+        </svg>
+        <article inner_html={Trusted::new(html)} />
+    </div>
+}"#
+    );
+}
+
+#[test]
+fn flags_what_needs_attention() {
+    let (output, warnings) = convert(
+        "const a = <Foo.Bar ref={r} {...props.rest} onHover={f}>{user?.name ?? null}</Foo.Bar>;",
+    );
+
+    assert_eq!(
+        output,
+        "const a = rsx! { <Foo::Bar ref={r} onHover={f}>{user?.name?? None}</Foo::Bar> };"
+    );
+    assert_eq!(
+        warnings,
+        [
+            "line 1: dropped the spread of `props . rest`, only identifiers can be spread; bind it to a variable first",
+            "line 1: `?.` needs manual attention",
+            "line 1: `??` needs manual attention",
+            "line 1: `null` became `None`",
+        ]
+    );
+}
+
+#[test]
+fn comparisons_are_not_jsx() {
+    let source = "if (a < b && c > d) { f(<br />) }";
+    let (output, _) = convert(source);
+
+    assert_eq!(output, "if (a < b && c > d) { f(rsx! { <br /> }) }");
+}
+
+#[test]
+fn reports_malformed_jsx() {
+    let err = jsx_to_rsx("const a = (\n  <div></span>\n);", &FormatOptions::default()).unwrap_err();
+
+    assert_eq!(err, "line 2: expected `</div>`, found `</span>`");
+}
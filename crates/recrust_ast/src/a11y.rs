@@ -0,0 +1,325 @@
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::LitStr;
+
+use crate::{
+    Diagnostic, ExprNode, RSXAttribute, RSXAttributeName, RSXElement, RSXNode,
+    content_model::INTERACTIVE_ELEMENTS,
+    diagnostics::Level,
+    schema::did_you_mean,
+    visit::{self, Visit},
+};
+
+// ---------------------------------- Accessibility ----------------------------------
+
+/// `aria-*` attributes defined by WAI-ARIA 1.2.
+pub const ARIA_ATTRIBUTES: &[&str] = &[
+    "aria-activedescendant",
+    "aria-atomic",
+    "aria-autocomplete",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-busy",
+    "aria-checked",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-controls",
+    "aria-current",
+    "aria-describedby",
+    "aria-description",
+    "aria-details",
+    "aria-disabled",
+    "aria-dropeffect",
+    "aria-errormessage",
+    "aria-expanded",
+    "aria-flowto",
+    "aria-grabbed",
+    "aria-haspopup",
+    "aria-hidden",
+    "aria-invalid",
+    "aria-keyshortcuts",
+    "aria-label",
+    "aria-labelledby",
+    "aria-level",
+    "aria-live",
+    "aria-modal",
+    "aria-multiline",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-owns",
+    "aria-placeholder",
+    "aria-posinset",
+    "aria-pressed",
+    "aria-readonly",
+    "aria-relevant",
+    "aria-required",
+    "aria-roledescription",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-selected",
+    "aria-setsize",
+    "aria-sort",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
+
+/// Non-abstract roles defined by WAI-ARIA 1.2.
+pub const ARIA_ROLES: &[&str] = &[
+    "alert",
+    "alertdialog",
+    "application",
+    "article",
+    "banner",
+    "blockquote",
+    "button",
+    "caption",
+    "cell",
+    "checkbox",
+    "code",
+    "columnheader",
+    "combobox",
+    "complementary",
+    "contentinfo",
+    "definition",
+    "deletion",
+    "dialog",
+    "document",
+    "emphasis",
+    "feed",
+    "figure",
+    "form",
+    "generic",
+    "grid",
+    "gridcell",
+    "group",
+    "heading",
+    "img",
+    "insertion",
+    "link",
+    "list",
+    "listbox",
+    "listitem",
+    "log",
+    "main",
+    "mark",
+    "marquee",
+    "math",
+    "menu",
+    "menubar",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "meter",
+    "navigation",
+    "none",
+    "note",
+    "option",
+    "paragraph",
+    "presentation",
+    "progressbar",
+    "radio",
+    "radiogroup",
+    "region",
+    "row",
+    "rowgroup",
+    "rowheader",
+    "scrollbar",
+    "search",
+    "searchbox",
+    "separator",
+    "slider",
+    "spinbutton",
+    "status",
+    "strong",
+    "subscript",
+    "superscript",
+    "switch",
+    "tab",
+    "table",
+    "tablist",
+    "tabpanel",
+    "term",
+    "textbox",
+    "time",
+    "timer",
+    "toolbar",
+    "tooltip",
+    "tree",
+    "treegrid",
+    "treeitem",
+];
+
+/// Input types that do not need a label, because they are hidden or labelled by their value.
+const UNLABELLED_INPUT_TYPES: &[&str] = &["hidden", "submit", "reset", "button", "image"];
+
+/// Runs the accessibility lints over every element and reports problems at the given level.
+///
+/// Elements with a `{..spread}` are skipped by the rules that look for a missing attribute, as
+/// the spread might provide it. Inputs with an `id` are assumed to be labelled by a
+/// `<label for=...>`, which might live in another template.
+pub fn check_accessibility(node: &RSXNode, level: Level) -> Vec<Diagnostic> {
+    let mut lints = Lints {
+        level,
+        labels: 0,
+        diagnostics: Vec::new(),
+    };
+    lints.visit_node(node);
+    lints.diagnostics
+}
+
+struct Lints {
+    level: Level,
+    /// Number of `<label>` ancestors of the current element
+    labels: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Lints {
+    fn report(&mut self, span: Span, message: String) {
+        self.diagnostics.push(Diagnostic {
+            level: self.level,
+            span,
+            message,
+        });
+    }
+
+    fn check_element(&mut self, element: &RSXElement) {
+        let tag = element.tag.to_string();
+        let attributes = &element.attributes;
+        let has = |name: &str| attributes.get(name).is_some();
+        let has_name = has("aria-label") || has("aria-labelledby") || has("title");
+
+        for attribute in &attributes.0 {
+            match attribute {
+                RSXAttribute::Normal { name, value, .. }
+                | RSXAttribute::Optional { name, value, .. } => {
+                    self.check_aria_attribute(name);
+                    if name == "role" {
+                        self.check_role(value);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Rules below look for missing attributes
+        if attributes.has_spread() {
+            return;
+        }
+
+        match tag.as_str() {
+            "img" if !has("alt") => self.report(
+                element.tag.span(),
+                "`<img>` is missing an `alt` attribute; use `alt={\"\"}` for decorative images"
+                    .to_string(),
+            ),
+            "button" if !(has_name || has("children") || has("inner_html")) => self.report(
+                element.tag.span(),
+                "`<button>` has no accessible name; add text content or an `aria-label`"
+                    .to_string(),
+            ),
+            "input" | "select" | "textarea" => {
+                let input_type = attributes
+                    .get("type")
+                    .and_then(RSXAttribute::value)
+                    .and_then(literal)
+                    .map(|lit| lit.value());
+                let needs_label = !input_type.is_some_and(|input_type| {
+                    UNLABELLED_INPUT_TYPES.contains(&input_type.as_str())
+                });
+
+                if needs_label && self.labels == 0 && !has_name && !has("id") {
+                    self.report(
+                        element.tag.span(),
+                        format!(
+                            "`<{}>` has no label; wrap it in a `<label>`, give it an `id` referenced by a `<label for=...>` or add an `aria-label`",
+                            tag
+                        ),
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        // Click handlers on elements that cannot be focused or activated with the keyboard
+        let on_click = attributes.0.iter().find_map(|attribute| match attribute {
+            RSXAttribute::Event { event, .. } if event == "click" => Some(event),
+            _ => None,
+        });
+        if let Some(event) = on_click
+            && !INTERACTIVE_ELEMENTS.contains(&tag.as_str())
+            && !has("role")
+        {
+            self.report(
+                event.span(),
+                format!(
+                    "`on:click` on the non-interactive `<{}>`; use a `<button>`, or add a `role`, a `tabindex` and a keyboard handler",
+                    tag
+                ),
+            );
+        }
+    }
+
+    fn check_aria_attribute(&mut self, name: &RSXAttributeName) {
+        if !name.value.starts_with("aria-") || ARIA_ATTRIBUTES.contains(&name.value.as_str()) {
+            return;
+        }
+
+        let message = match did_you_mean(&name.value, ARIA_ATTRIBUTES.iter().copied()) {
+            Some(suggestion) => format!(
+                "unknown ARIA attribute `{}`, did you mean `{}`?",
+                name, suggestion
+            ),
+            None => format!("unknown ARIA attribute `{}`", name),
+        };
+        self.report(name.span, message);
+    }
+
+    /// Only roles given as a string literal can be checked.
+    fn check_role(&mut self, value: &ExprNode) {
+        let Some(lit) = literal(value) else {
+            return;
+        };
+
+        for role in lit.value().split_whitespace() {
+            if ARIA_ROLES.contains(&role) {
+                continue;
+            }
+
+            let message = match did_you_mean(role, ARIA_ROLES.iter().copied()) {
+                Some(suggestion) => {
+                    format!(
+                        "unknown ARIA role `{}`, did you mean `{}`?",
+                        role, suggestion
+                    )
+                }
+                None => format!("unknown ARIA role `{}`", role),
+            };
+            self.report(lit.span(), message);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Lints {
+    fn visit_element(&mut self, element: &'ast RSXElement) {
+        self.check_element(element);
+
+        let label = element.tag == "label";
+        self.labels += usize::from(label);
+        visit::visit_element(self, element);
+        self.labels -= usize::from(label);
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// The value of an attribute, if it is a single string literal.
+fn literal(value: &ExprNode) -> Option<LitStr> {
+    syn::parse2(value.to_token_stream()).ok()
+}
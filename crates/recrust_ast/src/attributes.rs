@@ -1,10 +1,19 @@
+use std::fmt::Display;
+
+use proc_macro2::{Delimiter, Span, TokenStream};
+use quote::{ToTokens, quote};
 use syn::{
-    Ident, Token, braced,
+    Ident, LitInt, LitStr, Token, braced,
     ext::IdentExt,
     parse::{Parse, ParseStream},
 };
 
-use crate::raw_expr::ExprNode;
+use crate::{
+    events::event_type,
+    print::is_children,
+    raw_expr::{ExprNode, PartialExpr},
+    span::{AttributeSpans, SourceSpan},
+};
 
 // ---------------------------------- Macro Traits: Input / Output ----------------------------------
 
@@ -13,21 +22,281 @@ pub struct RSXAttributes(pub Vec<RSXAttribute>);
 
 impl Parse for RSXAttributes {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        match Self::parse_with_raw_marker(input)? {
+            (attributes, None) => Ok(attributes),
+            (_, Some(span)) => Err(syn::Error::new(
+                span,
+                "the `raw` marker is only allowed in an opening tag, like `<pre raw>`",
+            )),
+        }
+    }
+}
+
+impl RSXAttributes {
+    /// Parses the attributes of an opening tag, along with the `raw` marker that opts into raw
+    /// text content and may appear anywhere among them: `<pre class={c} raw>`.
+    pub(crate) fn parse_with_raw_marker(input: ParseStream) -> syn::Result<(Self, Option<Span>)> {
         let mut attributes = Vec::new();
+        let mut raw = None;
 
         while !(input.peek(Token![>]) || (input.peek(Token![/]) && input.peek2(Token![>]))) {
+            // `raw={...}`, `raw?={...}`, `raw:...` and `raw-...={...}` are normal attributes
+            if input.peek(kw::raw)
+                && !(input.peek2(Token![=])
+                    || input.peek2(Token![?])
+                    || input.peek2(Token![:])
+                    || input.peek2(Token![-]))
+            {
+                let span = input.parse::<kw::raw>()?.span;
+                if raw.is_some() {
+                    return Err(syn::Error::new(span, "duplicate `raw` marker"));
+                }
+                raw = Some(span);
+                continue;
+            }
+
             let attribute = input.parse::<RSXAttribute>()?;
+
+            if let Some((key, span)) = attribute.key()
+                && let Some((_, first_span)) = attributes
+                    .iter()
+                    .filter_map(RSXAttribute::key)
+                    .find(|(other, _)| *other == key)
+            {
+                return Err(duplicate_error(&key, span, first_span));
+            }
+
             attributes.push(attribute);
         }
 
-        Ok(Self(attributes))
+        Ok((Self(attributes), raw))
+    }
+
+    /// Finds a `name={...}` or `name?={...}` attribute.
+    pub fn get(&self, name: &str) -> Option<&RSXAttribute> {
+        self.0.iter().find(|attribute| match attribute {
+            RSXAttribute::Normal { name: n, .. }
+            | RSXAttribute::Optional { name: n, .. }
+            | RSXAttribute::InnerHtml { name: n, .. } => n == name,
+            _ => false,
+        })
+    }
+
+    /// Adds the nested children of an element or component as a `children` attribute.
+    pub fn push_children(&mut self, children: ExprNode, span: Span) -> syn::Result<()> {
+        if children.0.is_empty() {
+            return Ok(());
+        }
+
+        if let Some((_, first_span)) = self.get("children").and_then(RSXAttribute::key) {
+            return Err(duplicate_error("children", span, first_span));
+        }
+
+        // The children have no `=`, they span from the first to the last child
+        let full = match (children.0.first(), children.0.last()) {
+            (Some(PartialExpr::RSX(first)), Some(PartialExpr::RSX(last))) => {
+                first.span().to(last.span())
+            }
+            _ => span.into(),
+        };
+        self.0.push(RSXAttribute::Normal {
+            name: RSXAttributeName::new("children", span),
+            value: children,
+            spans: AttributeSpans { full, eq: None },
+        });
+        Ok(())
+    }
+
+    /// Whether a `{..spread}` might add attributes that are not known at compile time.
+    pub fn has_spread(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attribute| matches!(attribute, RSXAttribute::Spread { .. }))
+    }
+}
+
+/// An attribute name, which may contain dashes: `id`, `aria-label`, `data-user-id`.
+#[derive(Clone, Debug)]
+pub struct RSXAttributeName {
+    pub value: String,
+    /// Span of the first segment of the name
+    pub span: Span,
+}
+
+impl RSXAttributeName {
+    pub fn new(value: &str, span: Span) -> Self {
+        Self {
+            value: value.to_string(),
+            span,
+        }
+    }
+}
+
+impl Parse for RSXAttributeName {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let first = input.call(Ident::parse_any)?;
+        let mut value = first.to_string();
+
+        while input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            value.push('-');
+
+            if input.peek(LitInt) {
+                value.push_str(&input.parse::<LitInt>()?.to_string());
+            } else {
+                value.push_str(&input.call(Ident::parse_any)?.to_string());
+            }
+        }
+
+        Ok(Self {
+            value,
+            span: first.span(),
+        })
+    }
+}
+
+impl Display for RSXAttributeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl PartialEq<str> for RSXAttributeName {
+    fn eq(&self, other: &str) -> bool {
+        self.value == other
+    }
+}
+
+impl PartialEq<&str> for RSXAttributeName {
+    fn eq(&self, other: &&str) -> bool {
+        self.value == *other
     }
 }
 
+/// Attributes are pushed in source order and backends let later entries override earlier ones.
+/// Setting the same attribute twice is an error, but a `{..spread}` may set any of them:
+/// `<div {..defaults} id={"main"} />` overrides the `id` of the spread,
+/// `<div id={"main"} {..overrides} />` lets the spread override it.
+///
+/// Every attribute that contributes to the `class` attribute (`class={...}`, `class={[...]}`,
+/// `class:name={...}` and `class` entries of a spread) is pushed as its own `"class"` entry.
+/// Backends merge them into a single, deduplicated, whitespace separated `class` attribute.
+///
+/// Style directives are pushed as `"style:property"` entries, which backends merge with (and let
+/// override) the declarations of the literal `style` attribute.
 #[derive(Clone, Debug)]
 pub enum RSXAttribute {
-    Normal { name: Ident, value: ExprNode },
-    Spread { ident: Ident },
+    /// name={ ... }
+    Normal {
+        name: RSXAttributeName,
+        value: ExprNode,
+        spans: AttributeSpans,
+    },
+    /// name?={ ... }
+    /// The value is an `Option<T>`, the attribute is only emitted when it is `Some`.
+    Optional {
+        name: RSXAttributeName,
+        value: ExprNode,
+        spans: AttributeSpans,
+    },
+    /// on:event={ ... }
+    /// The handler is registered as a listener for a known DOM event.
+    Event {
+        event: Ident,
+        handler: ExprNode,
+        spans: AttributeSpans,
+    },
+    /// class:name={ ... }
+    /// The class is added when the `bool` condition is `true`.
+    Class {
+        class: LitStr,
+        condition: ExprNode,
+        spans: AttributeSpans,
+    },
+    /// class={[ "btn", if primary { "btn-primary" } else { "" } ]}
+    /// Each item of the list is added as a class, empty items are ignored by backends.
+    ClassList {
+        classes: ExprNode,
+        spans: AttributeSpans,
+    },
+    /// style:property={ ... }
+    /// A single style property, merged by backends with the literal `style` attribute.
+    Style {
+        property: LitStr,
+        value: ExprNode,
+        spans: AttributeSpans,
+    },
+    /// inner_html={ Trusted::new(html) }
+    /// Pre-rendered HTML inserted as is, the value must be wrapped in the `Trusted` marker type.
+    InnerHtml {
+        name: RSXAttributeName,
+        value: ExprNode,
+        spans: AttributeSpans,
+    },
+    /// {..attributes}
+    Spread { ident: Ident, spans: AttributeSpans },
+}
+
+impl RSXAttribute {
+    /// The name under which the attribute is emitted, used to detect duplicates.
+    /// Class lists and spreads can be combined with anything, so they have none.
+    pub fn key(&self) -> Option<(String, Span)> {
+        match self {
+            RSXAttribute::Normal { name, .. }
+            | RSXAttribute::Optional { name, .. }
+            | RSXAttribute::InnerHtml { name, .. } => Some((name.to_string(), name.span)),
+            RSXAttribute::Event { event, .. } => Some((format!("on:{}", event), event.span())),
+            RSXAttribute::Class { class, .. } => {
+                Some((format!("class:{}", class.value()), class.span()))
+            }
+            RSXAttribute::Style { property, .. } => {
+                Some((format!("style:{}", property.value()), property.span()))
+            }
+            RSXAttribute::ClassList { .. } | RSXAttribute::Spread { .. } => None,
+        }
+    }
+
+    /// Where the attribute is in the source.
+    pub fn spans(&self) -> &AttributeSpans {
+        match self {
+            RSXAttribute::Normal { spans, .. }
+            | RSXAttribute::Optional { spans, .. }
+            | RSXAttribute::Event { spans, .. }
+            | RSXAttribute::Class { spans, .. }
+            | RSXAttribute::ClassList { spans, .. }
+            | RSXAttribute::Style { spans, .. }
+            | RSXAttribute::InnerHtml { spans, .. }
+            | RSXAttribute::Spread { spans, .. } => spans,
+        }
+    }
+
+    /// The expression passed to the attribute, if any.
+    pub fn value(&self) -> Option<&ExprNode> {
+        match self {
+            RSXAttribute::Normal { value, .. }
+            | RSXAttribute::Optional { value, .. }
+            | RSXAttribute::Style { value, .. }
+            | RSXAttribute::InnerHtml { value, .. } => Some(value),
+            RSXAttribute::Event { handler, .. } => Some(handler),
+            RSXAttribute::Class { condition, .. } => Some(condition),
+            RSXAttribute::ClassList { classes, .. } => Some(classes),
+            RSXAttribute::Spread { .. } => None,
+        }
+    }
+
+    /// Mutable access to the expression passed to the attribute, if any.
+    pub fn value_mut(&mut self) -> Option<&mut ExprNode> {
+        match self {
+            RSXAttribute::Normal { value, .. }
+            | RSXAttribute::Optional { value, .. }
+            | RSXAttribute::Style { value, .. }
+            | RSXAttribute::InnerHtml { value, .. } => Some(value),
+            RSXAttribute::Event { handler, .. } => Some(handler),
+            RSXAttribute::Class { condition, .. } => Some(condition),
+            RSXAttribute::ClassList { classes, .. } => Some(classes),
+            RSXAttribute::Spread { .. } => None,
+        }
+    }
 }
 
 impl Parse for RSXAttribute {
@@ -35,24 +304,229 @@ impl Parse for RSXAttribute {
         // Spread: {..Attributes}
         if input.peek(syn::token::Brace) {
             let content;
-            braced!(content in input);
+            let brace = braced!(content in input);
             content.parse::<Token![..]>()?;
             return Ok(Self::Spread {
                 ident: content.parse()?,
+                spans: AttributeSpans {
+                    full: SourceSpan::new(brace.span.open(), brace.span.close()),
+                    eq: None,
+                },
             });
         }
 
+        let name = input.parse::<RSXAttributeName>()?;
+
+        // Directives: prefix:name = { ... }
+        if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+
+            return match name.to_string().as_str() {
+                // Event: on:event = { ... }
+                "on" => {
+                    let event = input.call(Ident::parse_any)?;
+                    if event_type(&event.to_string()).is_none() {
+                        return Err(syn::Error::new(
+                            event.span(),
+                            format!("unknown event `{}`", event),
+                        ));
+                    }
+
+                    let (handler, spans) = parse_value(input, name.span)?;
+                    Ok(Self::Event {
+                        event,
+                        handler,
+                        spans,
+                    })
+                }
+                // Class: class:btn-primary = { ... }
+                "class" => {
+                    let class = parse_dashed_name(input)?;
+                    let (condition, spans) = parse_value(input, name.span)?;
+                    Ok(Self::Class {
+                        class,
+                        condition,
+                        spans,
+                    })
+                }
+                // Style: style:background-color = { ... }
+                "style" => {
+                    let property = parse_dashed_name(input)?;
+                    let (value, spans) = parse_value(input, name.span)?;
+                    Ok(Self::Style {
+                        property,
+                        value,
+                        spans,
+                    })
+                }
+                _ => Err(syn::Error::new(
+                    name.span,
+                    format!(
+                        "unknown attribute directive `{}:`; expected `on:`, `class:` or `style:`",
+                        name
+                    ),
+                )),
+            };
+        }
+
+        // Optional: attrib_name ?= { ... }
+        if input.peek(Token![?]) {
+            input.parse::<Token![?]>()?;
+            let (value, spans) = parse_value(input, name.span)?;
+            return Ok(Self::Optional { name, value, spans });
+        }
+
         // Normal: attrib_name = { ... }
-        let name = input.call(Ident::parse_any)?;
+        let (value, spans) = parse_value(input, name.span)?;
 
-        input.parse::<Token![=]>()?;
+        // Trusted HTML: inner_html = { ... }
+        if name == "inner_html" {
+            return Ok(Self::InnerHtml { name, value, spans });
+        }
 
-        let content;
-        braced!(content in input);
+        // Class list: class = { [ ... ] }
+        if let [
+            PartialExpr::ExprNode {
+                delimiter: Delimiter::Bracket,
+                inner,
+                ..
+            },
+        ] = value.0.as_slice()
+            && name == "class"
+        {
+            return Ok(Self::ClassList {
+                classes: inner.clone(),
+                spans,
+            });
+        }
 
-        Ok(Self::Normal {
-            name,
-            value: content.parse()?,
-        })
+        Ok(Self::Normal { name, value, spans })
     }
 }
+
+/// Emits the statement(s) that add this attribute to the `__attrs` list
+/// built by elements and components.
+impl ToTokens for RSXAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            // Each child is converted to a node, together they are a fragment
+            RSXAttribute::Normal { value, .. } if is_children(self) => {
+                let children = value.0.iter().filter_map(|child| match child {
+                    PartialExpr::RSX(node) => Some(node),
+                    _ => None,
+                });
+                quote! {
+                    __attrs.push( ("children", create_fragment(vec![ #(create_child(#children)),* ])) );
+                }
+            }
+            RSXAttribute::Normal { name, value, .. } => {
+                let name_str = name.to_string();
+                quote! { __attrs.push( (#name_str, #value) ); }
+            }
+            RSXAttribute::Optional { name, value, .. } => {
+                let name_str = name.to_string();
+                quote! {
+                    if let ::core::option::Option::Some(__value) = { #value } {
+                        __attrs.push( (#name_str, __value) );
+                    }
+                }
+            }
+            RSXAttribute::Event { event, handler, .. } => {
+                let name_str = format!("on:{}", event);
+                let event_ty = Ident::new(
+                    event_type(&event.to_string()).expect("event was validated while parsing"),
+                    event.span(),
+                );
+                quote! { __attrs.push( (#name_str, create_listener::<#event_ty>( #handler )) ); }
+            }
+            RSXAttribute::Class {
+                class, condition, ..
+            } => {
+                quote! {
+                    if { #condition } {
+                        __attrs.push( ("class", #class) );
+                    }
+                }
+            }
+            RSXAttribute::ClassList { classes, .. } => {
+                quote! {
+                    for __class in [ #classes ] {
+                        __attrs.push( ("class", __class) );
+                    }
+                }
+            }
+            RSXAttribute::Style {
+                property, value, ..
+            } => {
+                let name_str = format!("style:{}", property.value());
+                quote! { __attrs.push( (#name_str, #value) ); }
+            }
+            RSXAttribute::InnerHtml { name, value, .. } => {
+                let name_str = name.to_string();
+                quote! {
+                    __attrs.push( (#name_str, {
+                        let __html: Trusted = { #value };
+                        create_inner_html(__html)
+                    }) );
+                }
+            }
+            RSXAttribute::Spread { ident, .. } => {
+                quote! { __attrs.extend( #ident ); }
+            }
+        });
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// `inner_html` replaces the content of the element, so it cannot be combined with children.
+pub fn check_inner_html(attributes: &RSXAttributes) -> syn::Result<()> {
+    let inner_html = attributes.0.iter().find_map(|attribute| match attribute {
+        RSXAttribute::InnerHtml { name, .. } => Some(name),
+        _ => None,
+    });
+    let has_children = attributes.0.iter().any(
+        |attribute| matches!(attribute, RSXAttribute::Normal { name, .. } if name == "children"),
+    );
+
+    match inner_html {
+        Some(name) if has_children => Err(syn::Error::new(
+            name.span,
+            "`inner_html` cannot be used on an element that also has children",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// An error pointing at both occurrences of a duplicated attribute.
+fn duplicate_error(key: &str, span: Span, first_span: Span) -> syn::Error {
+    let mut error = syn::Error::new(span, format!("duplicate attribute `{}`", key));
+    error.combine(syn::Error::new(
+        first_span,
+        format!("`{}` is first set here", key),
+    ));
+    error
+}
+
+/// Parses the `= { ... }` part of an attribute whose name starts at `start`.
+fn parse_value(input: ParseStream, start: Span) -> syn::Result<(ExprNode, AttributeSpans)> {
+    let eq = input.parse::<Token![=]>()?.span;
+
+    let content;
+    let brace = braced!(content in input);
+    let spans = AttributeSpans {
+        full: SourceSpan::new(start, brace.span.close()),
+        eq: Some(eq),
+    };
+    Ok((content.parse()?, spans))
+}
+
+/// Parses a name that may contain dashes, like `btn-primary`, `col-2` or `background-color`.
+fn parse_dashed_name(input: ParseStream) -> syn::Result<LitStr> {
+    let name = input.parse::<RSXAttributeName>()?;
+    Ok(LitStr::new(&name.value, name.span))
+}
+
+mod kw {
+    syn::custom_keyword!(raw);
+}
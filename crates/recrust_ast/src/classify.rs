@@ -0,0 +1,64 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::RSXTagName;
+
+// ---------------------------------- Element / Component Classification ----------------------------------
+
+/// Whether a tag is an intrinsic element or a component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagClass {
+    Element,
+    Component,
+}
+
+/// Decides whether a single identifier tag, like `div` or `Card`, is an element or a component.
+pub type Classifier = dyn Fn(&str) -> TagClass;
+
+thread_local! {
+    static CLASSIFIER: RefCell<Option<Rc<Classifier>>> = const { RefCell::new(None) };
+}
+
+/// Classifies a tag: paths (`<ui::Button>`) and marked tags (`<:my_component>`) are always
+/// components, other tags are left to the current classifier.
+pub fn classify(tag: &RSXTagName) -> TagClass {
+    if tag.marker.is_some() || tag.is_path() {
+        return TagClass::Component;
+    }
+
+    let name = tag.to_string();
+    match CLASSIFIER.with(|classifier| classifier.borrow().clone()) {
+        Some(classifier) => classifier(&name),
+        None => default_classifier(&name),
+    }
+}
+
+/// Tags starting with an ASCII lowercase letter are elements, anything else is a component.
+pub fn default_classifier(tag: &str) -> TagClass {
+    if tag.starts_with(|c: char| c.is_ascii_lowercase()) {
+        TagClass::Element
+    } else {
+        TagClass::Component
+    }
+}
+
+/// Runs `f`, parsing with a custom classifier for the tags that are not paths or marked.
+///
+/// For macros built on top of this crate:
+/// `with_classifier(|tag| ..., || syn::parse2::<RSXNode>(tokens))`.
+pub fn with_classifier<R>(
+    classifier: impl Fn(&str) -> TagClass + 'static,
+    f: impl FnOnce() -> R,
+) -> R {
+    /// Restores the previous classifier, even if `f` panics.
+    struct Restore(Option<Rc<Classifier>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CLASSIFIER.with(|classifier| *classifier.borrow_mut() = self.0.take());
+        }
+    }
+
+    let previous = CLASSIFIER.with(|current| current.replace(Some(Rc::new(classifier))));
+    let _restore = Restore(previous);
+    f()
+}
@@ -0,0 +1,200 @@
+use proc_macro2::Span;
+
+use crate::{
+    Diagnostic, RSXAttribute, RSXComponent, RSXElement, RSXNode,
+    diagnostics::Level,
+    visit::{self, Visit},
+};
+
+// ---------------------------------- Content Model ----------------------------------
+
+/// Elements that can only appear where flow content is expected, so never inside a `<p>`.
+pub const FLOW_ONLY_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "search",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Interactive elements, which cannot be nested inside `<a>` or `<button>`.
+pub const INTERACTIVE_ELEMENTS: &[&str] = &[
+    "a", "button", "details", "embed", "iframe", "input", "label", "select", "textarea",
+];
+
+/// Elements that must be a direct child of one of the given parents.
+pub const REQUIRED_PARENTS: &[(&str, &[&str])] = &[
+    ("li", &["ul", "ol", "menu"]),
+    ("dt", &["dl", "div"]),
+    ("dd", &["dl", "div"]),
+    ("td", &["tr"]),
+    ("th", &["tr"]),
+    ("tr", &["thead", "tbody", "tfoot"]),
+    ("thead", &["table"]),
+    ("tbody", &["table"]),
+    ("tfoot", &["table"]),
+    ("caption", &["table"]),
+    ("colgroup", &["table"]),
+    ("col", &["colgroup"]),
+    ("option", &["select", "datalist", "optgroup"]),
+    ("optgroup", &["select"]),
+    ("figcaption", &["figure"]),
+    ("summary", &["details"]),
+    ("legend", &["fieldset"]),
+    ("rt", &["ruby"]),
+    ("rp", &["ruby"]),
+];
+
+/// Walks the element tree and reports well-known content-model violations.
+///
+/// Violations that browsers silently repair (and which therefore break hydration) are errors,
+/// the others are warnings. RSX nested in expressions (e.g. `{items.map(|i| <li />)}`) counts as
+/// a child of the surrounding element, while the children of components and RSX in other
+/// attributes have an unknown parent and are only checked against their own descendants.
+pub fn check_content_model(node: &RSXNode) -> Vec<Diagnostic> {
+    let mut checker = ContentModel::default();
+    checker.visit_node(node);
+    checker.diagnostics
+}
+
+#[derive(Default)]
+struct ContentModel {
+    /// Tags of the elements the current node is nested in, when they are known
+    ancestors: Vec<String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ContentModel {
+    /// Visits with unknown ancestors, e.g. for RSX that is passed to a component.
+    fn detached(&mut self, f: impl FnOnce(&mut Self)) {
+        let ancestors = std::mem::take(&mut self.ancestors);
+        f(self);
+        self.ancestors = ancestors;
+    }
+}
+
+impl<'ast> Visit<'ast> for ContentModel {
+    fn visit_element(&mut self, element: &'ast RSXElement) {
+        let tag = element.tag.to_string();
+        check_nesting(
+            &tag,
+            element.tag.span(),
+            &self.ancestors,
+            &mut self.diagnostics,
+        );
+
+        self.ancestors.push(tag);
+        visit::visit_element(self, element);
+        self.ancestors.pop();
+    }
+
+    fn visit_component(&mut self, component: &'ast RSXComponent) {
+        self.detached(|checker| visit::visit_component(checker, component));
+    }
+
+    fn visit_attribute(&mut self, attribute: &'ast RSXAttribute) {
+        match attribute {
+            RSXAttribute::Normal { name, .. } if name == "children" => {
+                visit::visit_attribute(self, attribute)
+            }
+            _ => self.detached(|checker| visit::visit_attribute(checker, attribute)),
+        }
+    }
+}
+
+fn check_nesting(tag: &str, span: Span, ancestors: &[String], diagnostics: &mut Vec<Diagnostic>) {
+    let has_ancestor = |name: &str| ancestors.iter().any(|ancestor| ancestor == name);
+
+    // Block in `<p>`: browsers close the paragraph before the block
+    if has_ancestor("p") && FLOW_ONLY_ELEMENTS.contains(&tag) {
+        diagnostics.push(Diagnostic::error(
+            span,
+            format!(
+                "`<{}>` cannot be inside a `<p>`; browsers close the `<p>` before it",
+                tag
+            ),
+        ));
+    }
+
+    // Interactive in `<a>` / `<button>`: browsers close the outer one when the same tag is nested
+    if INTERACTIVE_ELEMENTS.contains(&tag) {
+        for container in ["a", "button"] {
+            if has_ancestor(container) {
+                let level = if tag == container {
+                    Level::Error
+                } else {
+                    Level::Warning
+                };
+                diagnostics.push(Diagnostic {
+                    level,
+                    span,
+                    message: format!(
+                        "interactive element `<{}>` cannot be inside a `<{}>`",
+                        tag, container
+                    ),
+                });
+            }
+        }
+    }
+
+    // Direct parents, only checked when the parent is known
+    let Some(parent) = ancestors.last() else {
+        return;
+    };
+
+    // `<tr>` in `<table>`: browsers insert a `<tbody>` around it
+    if tag == "tr" && parent == "table" {
+        diagnostics.push(Diagnostic::error(
+            span,
+            "`<tr>` cannot be a direct child of `<table>`; browsers wrap it in a `<tbody>`, add it explicitly",
+        ));
+        return;
+    }
+
+    let required = REQUIRED_PARENTS
+        .iter()
+        .find_map(|(child, parents)| (*child == tag).then_some(*parents));
+    if let Some(parents) = required
+        && !parents.contains(&parent.as_str())
+    {
+        let expected = parents
+            .iter()
+            .map(|parent| format!("`<{}>`", parent))
+            .collect::<Vec<_>>()
+            .join(", ");
+        diagnostics.push(Diagnostic::warning(
+            span,
+            format!(
+                "`<{}>` must be a direct child of {}, not `<{}>`",
+                tag, expected, parent
+            ),
+        ));
+    }
+}
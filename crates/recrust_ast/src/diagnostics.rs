@@ -0,0 +1,59 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote, quote_spanned};
+
+// ---------------------------------- Macro Traits: Output ----------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Warning,
+    Error,
+}
+
+/// A problem found by one of the checks that run over the parsed RSX.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub span: Span,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn warning(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            level: Level::Warning,
+            span,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            level: Level::Error,
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// Errors are emitted as `compile_error!`. Stable proc-macros cannot emit warnings,
+/// so warnings are emitted as the use of a deprecated constant at the offending span.
+impl ToTokens for Diagnostic {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let message = &self.message;
+
+        tokens.extend(match self.level {
+            Level::Error => syn::Error::new(self.span, message).to_compile_error(),
+            Level::Warning => {
+                let usage = quote_spanned! {self.span=> let _ = rsx_warning; };
+                quote! {
+                    {
+                        #[deprecated(note = #message)]
+                        #[allow(non_upper_case_globals)]
+                        const rsx_warning: () = ();
+                        #usage
+                    }
+                }
+            }
+        });
+    }
+}
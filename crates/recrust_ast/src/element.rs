@@ -1,99 +1,64 @@
-use proc_macro2::Span;
-use quote::{ToTokens, quote};
-use syn::{
-    Ident, Token,
-    ext::IdentExt,
-    parse::{Parse, ParseStream},
-};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
 
 use crate::{
-    PartialExpr, RSXAttribute, attributes::RSXAttributes, node::RSXNode, raw_expr::ExprNode,
+    classify::TagClass,
+    tag::{RSXTag, TagKind},
 };
 
 // ---------------------------------- Macro Traits: Input / Output ----------------------------------
 
-#[derive(Clone, Debug)]
-pub struct RSXElement {
-    pub tag: Ident,
-    pub attributes: RSXAttributes,
-}
-
-impl Parse for RSXElement {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        // Parse Opening Tag
-        input.parse::<Token![<]>()?;
-        let tag = input.call(Ident::parse_any)?;
-
-        // Parse attributes
-        let mut attributes = input.parse::<RSXAttributes>()?;
+/// An intrinsic element, whose tag is a single identifier or a custom element name:
+/// `<div ...>...</div>`, `<my-widget />`.
+/// See [`classify`](crate::classify) for how elements are told apart from components.
+pub type RSXElement = RSXTag<Intrinsic>;
 
-        // Handle self-closing tags: `<div />`
-        if input.peek(Token![/]) && input.peek2(Token![>]) {
-            input.parse::<Token![/]>()?;
-            input.parse::<Token![>]>()?;
-            return Ok(Self { tag, attributes });
-        }
-
-        // Handle normal opening tags: `<div>`
-        input.parse::<Token![>]>()?;
+/// The [`TagKind`] of intrinsic elements, which know about void and raw text elements and
+/// expand to their name as a string: `create_element("div", ...)`.
+#[derive(Clone, Copy, Debug)]
+pub struct Intrinsic;
 
-        // Parse children
-        let mut children = ExprNode(Vec::new());
-        while !(input.peek(Token![<]) && input.peek2(Token![/])) {
-            if input.is_empty() {
-                return Err(input.error("unexpected end of input while parsing children; expected a closing tag like `</...>`"));
-            }
+impl TagKind for Intrinsic {
+    const CLASS: TagClass = TagClass::Element;
 
-            children
-                .0
-                .push(PartialExpr::RSX(Box::new(input.parse::<RSXNode>()?)));
-        }
-        if !children.0.is_empty() {
-            attributes.0.push(RSXAttribute::Normal {
-                name: Ident::new("children", Span::call_site()),
-                value: children,
-            });
-        }
+    fn is_void(tag: &str) -> bool {
+        VOID_ELEMENTS.contains(&tag)
+    }
 
-        // Parse Closing Tag: `</div>`
-        input.parse::<Token![<]>()?;
-        input.parse::<Token![/]>()?;
-        let closing_tag = input.call(Ident::parse_any)?;
-        input.parse::<Token![>]>()?;
+    fn is_raw_text(tag: &str) -> bool {
+        RAW_TEXT_ELEMENTS.contains(&tag)
+    }
 
-        if closing_tag != tag {
+    /// Elements are single identifiers, the HTML schema is checked separately, see
+    /// [`check_schema`](crate::schema::check_schema).
+    fn check(element: &RSXElement) -> syn::Result<()> {
+        if element.tag.marker.is_some() || element.tag.is_path() {
             return Err(syn::Error::new(
-                closing_tag.span(),
-                format!("mismatched closing tag: expected `</{}>`", tag),
+                element.tag.span(),
+                format!("`<{}>` is a component, not an element", element.tag),
             ));
         }
 
-        Ok(Self { tag, attributes })
+        Ok(())
     }
-}
 
-impl ToTokens for RSXElement {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let tag_fn = self.tag.clone();
+    fn expand(element: &RSXElement, attributes: TokenStream) -> TokenStream {
+        let tag = LitStr::new(&element.tag.to_string(), element.tag.span());
 
-        let attributes = self.attributes.0.iter().map(|attribute| match attribute {
-            RSXAttribute::Normal { name, value } => {
-                let name_str = name.to_string();
-                quote! { __attrs.push( (#name_str, #value) ); }
-            }
-            RSXAttribute::Spread { ident } => {
-                quote! { __attrs.extend( #ident ); }
-            }
-        });
+        quote! {
+            create_element(#tag, #attributes)
+        }
+    }
+}
 
-        tokens.extend(quote! {
-            create_element(#tag_fn, {
-                let mut __attrs = Vec::new();
+// ---------------------------------- Other ----------------------------------
 
-                #(#attributes)*
+/// Elements whose content is always raw text, it is never parsed as RSX.
+pub const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
 
-                __attrs
-            })
-        });
-    }
-}
+/// Elements that cannot have any content, backends render them without a closing tag.
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
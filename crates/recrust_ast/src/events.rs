@@ -0,0 +1,87 @@
+// ---------------------------------- Event Types ----------------------------------
+
+/// DOM events accepted by `on:event={...}` attributes, mapped to the type of
+/// the payload the listener receives.
+pub const EVENTS: &[(&str, &str)] = &[
+    // Mouse
+    ("click", "MouseEvent"),
+    ("dblclick", "MouseEvent"),
+    ("contextmenu", "MouseEvent"),
+    ("mousedown", "MouseEvent"),
+    ("mouseup", "MouseEvent"),
+    ("mousemove", "MouseEvent"),
+    ("mouseenter", "MouseEvent"),
+    ("mouseleave", "MouseEvent"),
+    ("mouseover", "MouseEvent"),
+    ("mouseout", "MouseEvent"),
+    // Pointer
+    ("pointerdown", "PointerEvent"),
+    ("pointerup", "PointerEvent"),
+    ("pointermove", "PointerEvent"),
+    ("pointerenter", "PointerEvent"),
+    ("pointerleave", "PointerEvent"),
+    ("pointerover", "PointerEvent"),
+    ("pointerout", "PointerEvent"),
+    ("pointercancel", "PointerEvent"),
+    // Touch
+    ("touchstart", "TouchEvent"),
+    ("touchend", "TouchEvent"),
+    ("touchmove", "TouchEvent"),
+    ("touchcancel", "TouchEvent"),
+    // Wheel
+    ("wheel", "WheelEvent"),
+    // Keyboard
+    ("keydown", "KeyboardEvent"),
+    ("keyup", "KeyboardEvent"),
+    ("keypress", "KeyboardEvent"),
+    // Focus
+    ("focus", "FocusEvent"),
+    ("blur", "FocusEvent"),
+    ("focusin", "FocusEvent"),
+    ("focusout", "FocusEvent"),
+    // Forms
+    ("input", "InputEvent"),
+    ("beforeinput", "InputEvent"),
+    ("change", "Event"),
+    ("submit", "SubmitEvent"),
+    ("reset", "Event"),
+    ("invalid", "Event"),
+    // Composition
+    ("compositionstart", "CompositionEvent"),
+    ("compositionupdate", "CompositionEvent"),
+    ("compositionend", "CompositionEvent"),
+    // Drag & drop
+    ("drag", "DragEvent"),
+    ("dragstart", "DragEvent"),
+    ("dragend", "DragEvent"),
+    ("dragenter", "DragEvent"),
+    ("dragleave", "DragEvent"),
+    ("dragover", "DragEvent"),
+    ("drop", "DragEvent"),
+    // Clipboard
+    ("copy", "ClipboardEvent"),
+    ("cut", "ClipboardEvent"),
+    ("paste", "ClipboardEvent"),
+    // Animations & transitions
+    ("animationstart", "AnimationEvent"),
+    ("animationend", "AnimationEvent"),
+    ("animationiteration", "AnimationEvent"),
+    ("transitionstart", "TransitionEvent"),
+    ("transitionend", "TransitionEvent"),
+    ("transitionrun", "TransitionEvent"),
+    ("transitioncancel", "TransitionEvent"),
+    // Resources & misc
+    ("load", "Event"),
+    ("error", "Event"),
+    ("abort", "Event"),
+    ("scroll", "Event"),
+    ("select", "Event"),
+    ("toggle", "Event"),
+];
+
+/// Returns the payload type for a known DOM event name, e.g. `click` => `MouseEvent`.
+pub fn event_type(name: &str) -> Option<&'static str> {
+    EVENTS
+        .iter()
+        .find_map(|(event, ty)| (*event == name).then_some(*ty))
+}
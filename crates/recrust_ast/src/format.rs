@@ -0,0 +1,331 @@
+use std::ops::Range;
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+
+use crate::{Diagnostic, PrintOptions, RSXNode, print_rsx_with_comments};
+
+// ---------------------------------- Formatter ----------------------------------
+
+#[derive(Clone, Debug)]
+pub struct FormatOptions {
+    /// Inserted once per nesting level, inside the macro braces as well
+    pub indent: String,
+    /// Maximum line width, including the indentation of the macro invocation
+    pub max_width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: "    ".to_string(),
+            max_width: 100,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Formatted {
+    pub output: String,
+    /// Blocks that could not be formatted, and were left as they are
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Formats every `rsx! { ... }` invocation in a Rust source file.
+///
+/// Rust comments in front of a child are kept in front of it. Blocks that do not parse, have
+/// comments anywhere else, or would have a line wider than `max_width` (such as a long string),
+/// are left untouched and reported as warnings.
+pub fn format_source(source: &str, options: &FormatOptions) -> Formatted {
+    let tokens = match source.parse::<TokenStream>() {
+        Ok(tokens) => tokens,
+        Err(err) => {
+            return Formatted {
+                output: source.to_string(),
+                diagnostics: vec![Diagnostic::error(err.span(), err.to_string())],
+            };
+        }
+    };
+
+    let mut invocations = Vec::new();
+    find_invocations(tokens, &mut invocations);
+
+    let mut output = source.to_string();
+    let mut diagnostics = Vec::new();
+    // Replace from the end so the byte ranges of earlier invocations stay valid
+    for (bang, group) in invocations.iter().rev() {
+        match format_invocation(source, group, options) {
+            Ok(formatted) => output.replace_range(*bang..group.span().byte_range().end, &formatted),
+            Err(diagnostic) => diagnostics.push(diagnostic),
+        }
+    }
+    diagnostics.reverse();
+
+    Formatted {
+        output,
+        diagnostics,
+    }
+}
+
+/// Collects the delimited bodies of `rsx!` invocations, including ones nested in other macros,
+/// along with the end of their `!`.
+fn find_invocations(tokens: TokenStream, invocations: &mut Vec<(usize, Group)>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    for (index, token) in tokens.iter().enumerate() {
+        let TokenTree::Group(group) = token else {
+            continue;
+        };
+
+        let is_rsx = index >= 2
+            && matches!(&tokens[index - 2], TokenTree::Ident(ident) if ident == "rsx")
+            && matches!(&tokens[index - 1], TokenTree::Punct(punct) if punct.as_char() == '!');
+        if is_rsx && group.delimiter() != Delimiter::None {
+            let bang = tokens[index - 1].span().byte_range().end;
+            invocations.push((bang, group.clone()));
+        } else {
+            find_invocations(group.stream(), invocations);
+        }
+    }
+}
+
+/// Formats the delimited body of an invocation, along with the space after the `!`.
+fn format_invocation(
+    source: &str,
+    group: &Group,
+    options: &FormatOptions,
+) -> Result<String, Diagnostic> {
+    let node = syn::parse2::<RSXNode>(group.stream()).map_err(|err| {
+        // Errors at the end of the input have no position of their own
+        let span = if err.span().start().line == 0 {
+            group.span_open()
+        } else {
+            err.span()
+        };
+        Diagnostic::warning(span, format!("left unformatted: {}", err))
+    })?;
+
+    let (open, close) = match group.delimiter() {
+        Delimiter::Parenthesis => ("(", ")"),
+        Delimiter::Bracket => ("[", "]"),
+        _ => (" {", "}"),
+    };
+    // Braces are padded with spaces: `rsx! { <br /> }`
+    let padding = if close == "}" { " " } else { "" };
+
+    let start = group.span().byte_range().start;
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let base: String = source[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect();
+    // Column right after the `!`, assuming it is not followed by a line break
+    let column = source[line_start..start].trim_end().chars().count();
+
+    let print_options = PrintOptions {
+        indent: options.indent.clone(),
+        max_width: options
+            .max_width
+            .saturating_sub(base.chars().count() + options.indent.chars().count()),
+    };
+
+    let inner = group.span_open().byte_range().end..group.span_close().byte_range().start;
+    let mut comments = find_comments(source, inner);
+    let printed = print_rsx_with_comments(&node, &print_options, &mut |span| {
+        take_comments(source, &mut comments, span.byte_range().start)
+    });
+    if let Some(comment) = comments.iter().find(|comment| !comment.taken) {
+        return Err(Diagnostic::warning(
+            group.span_open(),
+            format!(
+                "left unformatted: only comments in front of a child can be preserved, found `{}`",
+                comment.text.lines().next().unwrap_or_default()
+            ),
+        ));
+    }
+
+    if !printed.contains('\n')
+        && column + open.len() + printed.chars().count() + 2 * padding.len() + close.len()
+            <= options.max_width
+    {
+        return Ok(format!("{open}{padding}{printed}{padding}{close}"));
+    }
+
+    let mut formatted = format!("{open}\n");
+    for line in printed.lines() {
+        if !line.is_empty() {
+            let width =
+                base.chars().count() + options.indent.chars().count() + line.chars().count();
+            if width > options.max_width {
+                return Err(Diagnostic::warning(
+                    group.span_open(),
+                    format!(
+                        "left unformatted: `{}` would be wider than {} columns",
+                        line.trim(),
+                        options.max_width
+                    ),
+                ));
+            }
+            formatted.push_str(&base);
+            formatted.push_str(&options.indent);
+            formatted.push_str(line);
+        }
+        formatted.push('\n');
+    }
+    formatted.push_str(&base);
+    formatted.push_str(close);
+    Ok(formatted)
+}
+
+// ---------------------------------- Comments ----------------------------------
+
+struct Comment {
+    range: Range<usize>,
+    text: String,
+    taken: bool,
+}
+
+/// Finds the `//` and `/* */` comments in a range of Rust source, skipping string and char
+/// literals.
+fn find_comments(source: &str, range: Range<usize>) -> Vec<Comment> {
+    let bytes = source.as_bytes();
+    let is_ident = |i: usize| bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_';
+    let mut comments = Vec::new();
+
+    let mut i = range.start;
+    while i < range.end {
+        let next = bytes.get(i + 1).copied();
+        match bytes[i] {
+            b'/' if next == Some(b'/') => {
+                let end = source[i..range.end].find('\n').map_or(range.end, |n| i + n);
+                comments.push(Comment {
+                    range: i..end,
+                    text: source[i..end].trim_end().to_string(),
+                    taken: false,
+                });
+                i = end;
+            }
+            b'/' if next == Some(b'*') => {
+                let mut depth = 0;
+                let mut end = i;
+                while end < range.end {
+                    if bytes[end..].starts_with(b"/*") {
+                        depth += 1;
+                        end += 2;
+                    } else if bytes[end..].starts_with(b"*/") {
+                        depth -= 1;
+                        end += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        end += 1;
+                    }
+                }
+                // Continuation lines are re-indented along with the comment
+                let text = source[i..end]
+                    .lines()
+                    .enumerate()
+                    .map(|(n, line)| match line.trim_start() {
+                        line if n > 0 && line.starts_with('*') => format!(" {}", line),
+                        trimmed if n > 0 => trimmed.to_string(),
+                        _ => line.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                comments.push(Comment {
+                    range: i..end,
+                    text,
+                    taken: false,
+                });
+                i = end;
+            }
+            b'"' => i = skip_string(bytes, i + 1),
+            // Raw strings: `r"..."`, `r#"..."#`, `br"..."`
+            b'r' if (i == range.start
+                || !is_ident(i - 1)
+                || (bytes[i - 1] == b'b' && (i - 1 == range.start || !is_ident(i - 2))))
+                && matches!(next, Some(b'"' | b'#')) =>
+            {
+                let hashes = bytes[i + 1..].iter().take_while(|b| **b == b'#').count();
+                if bytes.get(i + 1 + hashes) != Some(&b'"') {
+                    i += 1;
+                    continue;
+                }
+                let closing = format!("\"{}", "#".repeat(hashes));
+                let body = i + 2 + hashes;
+                i = source[body..]
+                    .find(&closing)
+                    .map_or(bytes.len(), |n| body + n + closing.len());
+            }
+            // Char literals, as opposed to lifetimes and labels
+            b'\'' if next == Some(b'\\') => {
+                let close = source[i + 3..]
+                    .find('\'')
+                    .map_or(bytes.len(), |n| i + 3 + n);
+                i = close + 1;
+            }
+            b'\'' => {
+                let width = source[i + 1..].chars().next().map_or(1, char::len_utf8);
+                if bytes.get(i + 1 + width) == Some(&b'\'') {
+                    i += width + 2;
+                } else {
+                    i += 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    comments
+}
+
+fn skip_string(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    i
+}
+
+/// Takes the comments directly in front of a node starting at `anchor`, with nothing but
+/// whitespace in between.
+fn take_comments(source: &str, comments: &mut [Comment], anchor: usize) -> Vec<String> {
+    let mut cursor = anchor;
+    let mut taken = Vec::new();
+
+    for (index, comment) in comments.iter().enumerate().rev() {
+        if comment.range.end > anchor {
+            continue;
+        }
+
+        if comment.taken || !source[comment.range.end..cursor].trim().is_empty() {
+            break;
+        }
+
+        taken.push(index);
+        cursor = comment.range.start;
+    }
+
+    taken
+        .into_iter()
+        .rev()
+        .flat_map(|index| {
+            comments[index].taken = true;
+            comments[index]
+                .text
+                .lines()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// Position of a span as 1-based line and column, for messages.
+pub fn line_column(span: Span) -> (usize, usize) {
+    let start = span.start();
+    (start.line, start.column + 1)
+}
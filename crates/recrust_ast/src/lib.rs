@@ -1,11 +1,38 @@
+pub mod a11y;
 pub mod attributes;
+pub mod classify;
 pub mod component;
+pub mod content_model;
+pub mod diagnostics;
 pub mod element;
+pub mod events;
+pub mod format;
 pub mod node;
+pub mod print;
 pub mod raw_expr;
+pub mod schema;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod span;
+pub mod tag;
+pub mod template;
+pub mod text;
+pub mod visit;
+pub mod visit_mut;
 
+pub use a11y::*;
 pub use attributes::*;
+pub use classify::*;
 pub use component::*;
+pub use content_model::*;
+pub use diagnostics::*;
 pub use element::*;
+pub use events::*;
+pub use format::*;
 pub use node::*;
+pub use print::*;
 pub use raw_expr::*;
+pub use span::*;
+pub use tag::*;
+pub use template::*;
+pub use text::*;
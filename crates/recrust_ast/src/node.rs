@@ -1,14 +1,13 @@
 use std::fmt::Debug;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, TokenTree};
 use quote::ToTokens;
 use syn::{
-    Ident, LitStr, Token, braced,
-    ext::IdentExt,
+    LitStr, Token, braced,
     parse::{Parse, ParseStream},
 };
 
-use crate::{RSXComponent, RSXElement, ExprNode};
+use crate::{ExprNode, RSXComponent, RSXElement, RSXTagName, SourceSpan, TagClass, classify};
 
 // ---------------------------------- Macro Traits: Input / Output ----------------------------------
 
@@ -20,32 +19,45 @@ pub enum RSXNode {
     RSXComponent(RSXComponent),
     /// "Hello, world!"
     Text(LitStr),
+    /// <style>"p { color: red; }"</style>
+    /// The content of a raw text element, which backends emit without escaping.
+    RawText(LitStr),
+    /// <!-- "Hello, world!" -->
+    /// Either a single string literal or any tokens, which are kept as their source text.
+    /// The span goes from the `<` of `<!--` to the `>` of `-->`.
+    Comment(LitStr, SourceSpan),
     /// { 1 + 2 * 3 }
     /// This also finds and expands any nested RSX code inside the `{ ... }` block.
-    RawExpr(ExprNode),
+    /// The span goes from `{` to `}`.
+    RawExpr(ExprNode, SourceSpan),
 }
 
 impl Parse for RSXNode {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `<!--` = comment
+        if input.peek(Token![<]) && input.peek2(Token![!]) {
+            let (text, span) = parse_comment(input)?;
+            Ok(RSXNode::Comment(text, span))
+        }
         // `<` = element or component
-        if input.peek(Token![<]) {
+        else if input.peek(Token![<]) {
             let fork = input.fork();
 
             fork.parse::<Token![<]>()?;
-            let tag = fork.call(Ident::parse_any)?.to_string();
+            let tag = fork.parse::<RSXTagName>()?;
 
-            // If the tag is lowercase, it's an element, otherwise it's a component
-            if tag.chars().next().unwrap().is_ascii_lowercase() {
-                Ok(RSXNode::RSXElement(input.parse()?))
-            } else {
-                Ok(RSXNode::RSXComponent(input.parse()?))
+            // Paths and `<:name>` are components, the classifier decides for the others
+            match classify(&tag) {
+                TagClass::Element => Ok(RSXNode::RSXElement(input.parse()?)),
+                TagClass::Component => Ok(RSXNode::RSXComponent(input.parse()?)),
             }
         }
         // `{` = raw expression
         else if input.peek(syn::token::Brace) {
             let content;
-            braced!(content in input);
-            Ok(RSXNode::RawExpr(content.parse()?))
+            let brace = braced!(content in input);
+            let span = SourceSpan::new(brace.span.open(), brace.span.close());
+            Ok(RSXNode::RawExpr(content.parse()?, span))
         }
         // Just a plain string literal
         else if input.peek(syn::LitStr) {
@@ -64,21 +76,89 @@ impl ToTokens for RSXNode {
             RSXNode::RSXElement(element) => element.to_tokens(tokens),
             RSXNode::RSXComponent(component) => component.to_tokens(tokens),
             // Important to add back the braces to the raw expression
-            RSXNode::RawExpr(raw_expr) => tokens.extend(quote::quote!({ #raw_expr })),
+            RSXNode::RawExpr(raw_expr, _) => tokens.extend(quote::quote!({ #raw_expr })),
             RSXNode::Text(text) => text.to_tokens(tokens),
+            RSXNode::RawText(text) => tokens.extend(quote::quote!(create_raw_text(#text))),
+            RSXNode::Comment(text, _) => tokens.extend(quote::quote!(create_comment(#text))),
         }
     }
 }
 
 // ---------------------------------- Other ----------------------------------
 
+impl RSXNode {
+    /// Where the whole node is in the source.
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            RSXNode::RSXElement(element) => element.spans.full,
+            RSXNode::RSXComponent(component) => component.spans.full,
+            RSXNode::Text(text) | RSXNode::RawText(text) => text.span().into(),
+            RSXNode::Comment(_, span) | RSXNode::RawExpr(_, span) => *span,
+        }
+    }
+}
+
 impl Debug for RSXNode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             RSXNode::RSXElement(element) => write!(f, "Element({:?})", element),
             RSXNode::RSXComponent(component) => write!(f, "Component({:?})", component),
-            RSXNode::RawExpr(raw_expr) => write!(f, "RawExpr({:?})", raw_expr),
+            RSXNode::RawExpr(raw_expr, _) => write!(f, "RawExpr({:?})", raw_expr),
             RSXNode::Text(text) => write!(f, "Text({:?})", text.to_token_stream().to_string()),
+            RSXNode::RawText(text) => write!(f, "RawText({:?})", text.value()),
+            RSXNode::Comment(text, _) => write!(f, "Comment({:?})", text.value()),
+        }
+    }
+}
+
+/// Parses an HTML style comment: `<!-- ... -->`
+fn parse_comment(input: ParseStream) -> syn::Result<(LitStr, SourceSpan)> {
+    let start = input.span();
+    input.parse::<Token![<]>()?;
+    input.parse::<Token![!]>()?;
+    input.parse::<Token![-]>()?;
+    input.parse::<Token![-]>()?;
+
+    let mut content = Vec::new();
+    while !(input.peek(Token![-]) && input.peek2(Token![->])) {
+        if input.is_empty() {
+            return Err(syn::Error::new(
+                start,
+                "unterminated comment; expected a closing `-->`",
+            ));
+        }
+        content.push(input.parse::<TokenTree>()?);
+    }
+
+    input.parse::<Token![-]>()?;
+    let end = input.parse::<Token![->]>()?.spans[1];
+
+    // A single string literal keeps its value, anything else is kept as source text
+    let text = match syn::parse2::<LitStr>(content.iter().cloned().collect()) {
+        Ok(text) => text,
+        Err(_) => {
+            let span = content.first().map_or(start, TokenTree::span);
+            LitStr::new(&source_text(&content), span)
+        }
+    };
+    Ok((text, SourceSpan::new(start, end)))
+}
+
+/// The source text of tokens, with a space wherever the source has whitespace. Falls back to
+/// their `to_string` when the source is unknown, e.g. for tokens built with `quote!`.
+fn source_text(tokens: &[TokenTree]) -> String {
+    let mut text = String::new();
+    let mut previous_end = None;
+    for token in tokens {
+        let span = token.span();
+        let Some(source) = span.source_text() else {
+            return tokens.iter().cloned().collect::<TokenStream>().to_string();
+        };
+        if previous_end.is_some_and(|end| end != span.start()) {
+            text.push(' ');
         }
+        text.push_str(&source);
+        previous_end = Some(span.end());
     }
+    text
 }
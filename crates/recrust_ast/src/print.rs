@@ -0,0 +1,568 @@
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::LitStr;
+
+use crate::{
+    ExprNode, PartialExpr, RSXAttribute, RSXAttributes, RSXNode, RSXTag, SourceSpan, TagClass,
+    TagKind, default_classifier,
+};
+
+// ---------------------------------- Printer ----------------------------------
+
+#[derive(Clone, Debug)]
+pub struct PrintOptions {
+    /// Inserted once per nesting level in front of children
+    pub indent: String,
+    /// Opening tags longer than this are wrapped, one attribute per line
+    pub max_width: usize,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            indent: "    ".to_string(),
+            max_width: 100,
+        }
+    }
+}
+
+/// Prints a parsed node back to RSX source.
+///
+/// Children are printed one per line, unless the only child is text or an expression. RSX nested
+/// inside expressions is printed on a single line. Elements and components without children are
+/// always printed self-closing.
+///
+/// Expressions and attributes written over several lines are kept as written, re-indented to
+/// their new depth, since they are only printed on one line.
+pub fn print_rsx(node: &RSXNode, options: &PrintOptions) -> String {
+    print_rsx_with_comments(node, options, &mut |_| Vec::new())
+}
+
+/// Same as [`print_rsx`], but asks `comments` for the comments to print on their own lines in
+/// front of each node (the root and every child printed on its own line), given the span of the
+/// node's first token (the `<` for elements and components, the `{` for expressions).
+pub fn print_rsx_with_comments(
+    node: &RSXNode,
+    options: &PrintOptions,
+    comments: &mut dyn FnMut(Span) -> Vec<String>,
+) -> String {
+    let mut printer = Printer {
+        options,
+        out: String::new(),
+        comments,
+    };
+    for comment in (printer.comments)(node.span().start) {
+        printer.out.push_str(&comment);
+        printer.newline(0);
+    }
+    printer.node(node, 0, true);
+    printer.out
+}
+
+/// Prints a Rust expression that may contain RSX, on a single line.
+pub fn print_expr(expr: &ExprNode) -> String {
+    let options = PrintOptions::default();
+    let mut printer = Printer {
+        options: &options,
+        out: String::new(),
+        comments: &mut |_| Vec::new(),
+    };
+    printer.expr(expr);
+    printer.out
+}
+
+struct Printer<'a> {
+    options: &'a PrintOptions,
+    out: String,
+    comments: &'a mut dyn FnMut(Span) -> Vec<String>,
+}
+
+impl Printer<'_> {
+    fn newline(&mut self, depth: usize) {
+        self.out.push('\n');
+        for _ in 0..depth {
+            self.out.push_str(&self.options.indent);
+        }
+    }
+
+    fn node(&mut self, node: &RSXNode, depth: usize, multiline: bool) {
+        match node {
+            RSXNode::RSXElement(element) => self.tag(element, depth, multiline),
+            RSXNode::RSXComponent(component) => self.tag(component, depth, multiline),
+            RSXNode::Text(text) | RSXNode::RawText(text) => {
+                self.out.push_str(&text.to_token_stream().to_string())
+            }
+            RSXNode::Comment(text, _) => match comment_text(text) {
+                text if text.is_empty() => self.out.push_str("<!-- -->"),
+                text => {
+                    self.out.push_str("<!-- ");
+                    self.out.push_str(&text);
+                    self.out.push_str(" -->");
+                }
+            },
+            RSXNode::RawExpr(expr, span) => {
+                if multiline && let Some(source) = multiline_source(*span, expr) {
+                    self.push_lines(&source, depth);
+                    return;
+                }
+                self.out.push('{');
+                self.expr(expr);
+                self.out.push('}');
+            }
+        }
+    }
+
+    fn tag<K: TagKind>(&mut self, node: &RSXTag<K>, depth: usize, multiline: bool) {
+        let tag = &node.tag.to_string();
+        let attributes = &node.attributes;
+        let children = children(attributes);
+
+        // The `raw` marker is only needed for tags that are not raw text by default
+        let mut head = Vec::new();
+        if matches!(children[..], [RSXNode::RawText(_)]) && !K::is_raw_text(tag) {
+            head.push("raw".to_string());
+        }
+        for attribute in &attributes.0 {
+            if is_children(attribute) {
+                continue;
+            }
+            let source = attribute
+                .value()
+                .filter(|_| multiline)
+                .and_then(|value| multiline_source(attribute.spans().full, value));
+            head.push(source.unwrap_or_else(|| self.attribute_string(attribute)));
+        }
+
+        let self_closing = children.is_empty() || K::is_void(tag);
+
+        // Keep the marker of components that would otherwise be read back as elements
+        let name = if node.tag.marker.is_some()
+            || (K::CLASS == TagClass::Component
+                && !node.tag.is_path()
+                && default_classifier(tag) == TagClass::Element)
+        {
+            format!(":{}", tag)
+        } else {
+            tag.to_string()
+        };
+        let end = if self_closing { " />" } else { ">" };
+
+        // Wrap the opening tag, one attribute per line, when it does not fit
+        let width = depth * self.options.indent.chars().count()
+            + 1
+            + name.chars().count()
+            + head.iter().map(|h| h.chars().count() + 1).sum::<usize>()
+            + end.len();
+        let wrap = multiline
+            && !head.is_empty()
+            && (width > self.options.max_width || head.iter().any(|h| h.contains('\n')));
+
+        self.out.push('<');
+        self.out.push_str(&name);
+        for h in &head {
+            if wrap {
+                self.newline(depth + 1);
+            } else {
+                self.out.push(' ');
+            }
+            self.push_lines(h, depth + 1);
+        }
+        if wrap {
+            self.newline(depth);
+            self.out.push_str(end.trim_start());
+        } else {
+            self.out.push_str(end);
+        }
+        if self_closing {
+            return;
+        }
+
+        let comments: Vec<Vec<String>> = children
+            .iter()
+            .map(|child| {
+                if multiline {
+                    (self.comments)(child.span().start)
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect();
+
+        let mut inline = !multiline
+            || (comments.iter().all(Vec::is_empty)
+                && match children[..] {
+                    [RSXNode::Text(_) | RSXNode::RawText(_)] => true,
+                    [RSXNode::RawExpr(expr, span)] => multiline_source(*span, expr).is_none(),
+                    _ => false,
+                });
+        // A single child stays next to the tags only if the closing tag fits as well
+        if multiline && inline {
+            let column = if wrap {
+                depth * self.options.indent.chars().count() + 1
+            } else {
+                width
+            };
+            let child = self.node_string(children[0], depth + 1);
+            inline =
+                column + child.chars().count() + name.chars().count() + 3 <= self.options.max_width;
+        }
+        for (child, comments) in children.into_iter().zip(comments) {
+            if !inline {
+                for comment in comments {
+                    self.newline(depth + 1);
+                    self.out.push_str(&comment);
+                }
+                self.newline(depth + 1);
+            }
+            self.node(child, depth + 1, multiline);
+        }
+        if !inline {
+            self.newline(depth);
+        }
+
+        self.out.push_str("</");
+        self.out.push_str(&name);
+        self.out.push('>');
+    }
+
+    /// Pushes text whose lines after the first are indented relative to `depth`.
+    fn push_lines(&mut self, text: &str, depth: usize) {
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 && line.is_empty() {
+                self.out.push('\n');
+            } else if index > 0 {
+                self.newline(depth);
+            }
+            self.out.push_str(line);
+        }
+    }
+
+    fn node_string(&mut self, node: &RSXNode, depth: usize) -> String {
+        let out = std::mem::take(&mut self.out);
+        self.node(node, depth, true);
+        std::mem::replace(&mut self.out, out)
+    }
+
+    fn attribute_string(&mut self, attribute: &RSXAttribute) -> String {
+        let out = std::mem::take(&mut self.out);
+        self.attribute(attribute);
+        std::mem::replace(&mut self.out, out)
+    }
+
+    fn attribute(&mut self, attribute: &RSXAttribute) {
+        let (name, value) = match attribute {
+            RSXAttribute::Normal { name, value, .. }
+            | RSXAttribute::InnerHtml { name, value, .. } => (name.to_string(), value),
+            RSXAttribute::Optional { name, value, .. } => (format!("{}?", name), value),
+            RSXAttribute::Event { event, handler, .. } => (format!("on:{}", event), handler),
+            RSXAttribute::Class {
+                class, condition, ..
+            } => (format!("class:{}", class.value()), condition),
+            RSXAttribute::Style {
+                property, value, ..
+            } => (format!("style:{}", property.value()), value),
+            RSXAttribute::ClassList { classes, .. } => {
+                self.out.push_str("class={[");
+                self.expr(classes);
+                self.out.push_str("]}");
+                return;
+            }
+            RSXAttribute::Spread { ident, .. } => {
+                self.out.push_str(&format!("{{..{}}}", ident));
+                return;
+            }
+        };
+
+        self.out.push_str(&name);
+        self.out.push_str("={");
+        self.expr(value);
+        self.out.push('}');
+    }
+
+    /// Prints Rust code with nested RSX, spacing the tokens the way `rustfmt` would in most cases.
+    fn expr(&mut self, expr: &ExprNode) {
+        let mut spacer = Spacer::default();
+        self.expr_parts(expr, &mut spacer);
+    }
+
+    fn expr_parts(&mut self, expr: &ExprNode, spacer: &mut Spacer) {
+        for part in &expr.0 {
+            match part {
+                PartialExpr::Normal(tokens) => self.tokens(tokens.clone(), spacer),
+                PartialExpr::RSX(node) => {
+                    spacer.space_before(&mut self.out, &Token::Atom);
+                    self.node(node, 0, false);
+                    spacer.after(Token::Atom);
+                }
+                PartialExpr::ExprNode {
+                    delimiter, inner, ..
+                } => self.group(
+                    *delimiter,
+                    |printer, spacer| printer.expr_parts(inner, spacer),
+                    inner.0.is_empty(),
+                    spacer,
+                ),
+            }
+        }
+    }
+
+    fn tokens(&mut self, tokens: TokenStream, spacer: &mut Spacer) {
+        for token in tokens {
+            match token {
+                TokenTree::Group(group) => {
+                    let empty = group.stream().is_empty();
+                    self.group(
+                        group.delimiter(),
+                        |printer, spacer| printer.tokens(group.stream(), spacer),
+                        empty,
+                        spacer,
+                    )
+                }
+                TokenTree::Punct(punct) => {
+                    let token = Token::Punct(punct.as_char(), punct.spacing());
+                    spacer.space_before(&mut self.out, &token);
+                    self.out.push(punct.as_char());
+                    spacer.after(token);
+                }
+                TokenTree::Ident(ident) => {
+                    let token = Token::Ident(ident.to_string());
+                    spacer.space_before(&mut self.out, &token);
+                    self.out.push_str(&ident.to_string());
+                    spacer.after(token);
+                }
+                TokenTree::Literal(literal) => {
+                    spacer.space_before(&mut self.out, &Token::Atom);
+                    self.out.push_str(&literal.to_string());
+                    spacer.after(Token::Atom);
+                }
+            }
+        }
+    }
+
+    fn group(
+        &mut self,
+        delimiter: Delimiter,
+        inner: impl FnOnce(&mut Self, &mut Spacer),
+        empty: bool,
+        spacer: &mut Spacer,
+    ) {
+        let (open, close) = match delimiter {
+            Delimiter::Parenthesis => ("(", ")"),
+            Delimiter::Bracket => ("[", "]"),
+            // Braces are padded with spaces: `if a { b } else { c }`
+            Delimiter::Brace if empty => ("{", "}"),
+            Delimiter::Brace => ("{ ", " }"),
+            Delimiter::None => ("", ""),
+        };
+
+        spacer.space_before(&mut self.out, &Token::Open(delimiter));
+        self.out.push_str(open);
+
+        let mut inner_spacer = Spacer::default();
+        inner(self, &mut inner_spacer);
+
+        self.out.push_str(close);
+        spacer.after(Token::Close);
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// Children parsed from between the tags are stored as a `children` attribute made of RSX nodes.
+pub(crate) fn is_children(attribute: &RSXAttribute) -> bool {
+    matches!(attribute, RSXAttribute::Normal { name, value, .. }
+        if name == "children" && value.0.iter().all(|part| matches!(part, PartialExpr::RSX(_))))
+}
+
+pub(crate) fn children(attributes: &RSXAttributes) -> Vec<&RSXNode> {
+    attributes
+        .0
+        .iter()
+        .filter(|attribute| is_children(attribute))
+        .filter_map(RSXAttribute::value)
+        .flat_map(|value| &value.0)
+        .filter_map(|part| match part {
+            PartialExpr::RSX(node) => Some(node.as_ref()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The content of a comment as written: its literal, or its source text when it wasn't a literal.
+/// Comments without a known source, e.g. built with `quote!`, are printed as a literal.
+fn comment_text(text: &LitStr) -> String {
+    match text.span().source_text() {
+        Some(source) if syn::parse_str::<LitStr>(&source).is_ok_and(|lit| lit == *text) => source,
+        Some(_) => text.value(),
+        None => text.to_token_stream().to_string(),
+    }
+}
+
+/// The source text of code written over several lines, with the lines after the first indented
+/// relative to where it starts, or to its least indented line. `None` for code on a single line,
+/// without a known source, or with a string literal over several lines, which can't be
+/// re-indented without changing it.
+fn multiline_source(span: SourceSpan, expr: &ExprNode) -> Option<String> {
+    let source = span.joined().source_text()?;
+    if !source.contains('\n') || has_multiline_literal(expr.to_token_stream()) {
+        return None;
+    }
+
+    let indent = |line: &str| line.chars().take_while(|c| c.is_whitespace()).count();
+    let base = source
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(indent)
+        .chain([span.start.start().column])
+        .min()
+        .unwrap_or_default();
+
+    let lines: Vec<String> = source
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let skip = if index == 0 {
+                0
+            } else {
+                base.min(indent(line))
+            };
+            line.chars()
+                .skip(skip)
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+fn has_multiline_literal(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Literal(literal) => literal.to_string().contains('\n'),
+        TokenTree::Group(group) => has_multiline_literal(group.stream()),
+        _ => false,
+    })
+}
+
+/// Keywords that are followed by a space, even before a parenthesis.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "break", "const", "continue", "dyn", "else", "for", "if", "impl", "in", "let",
+    "loop", "match", "move", "mut", "ref", "return", "static", "unsafe", "where", "while",
+];
+
+#[derive(Clone, Debug, Default, PartialEq)]
+enum Token {
+    #[default]
+    Start,
+    Ident(String),
+    /// Literals and nested RSX
+    Atom,
+    Punct(char, Spacing),
+    Open(Delimiter),
+    Close,
+}
+
+/// Decides whether a space goes between two tokens.
+#[derive(Default)]
+struct Spacer {
+    prev: Token,
+    /// Whether the previous punctuation is unary (`-x`, `&x`, `!x`) and sticks to what follows
+    unary: bool,
+    /// Whether we are between the pipes of closure parameters: `|a, b|`
+    closure_params: bool,
+    /// Whether the previous `:` was part of a `::`
+    path: bool,
+    /// Depth of the turbofish generics we are in: `::<Vec<_>>`
+    generics: usize,
+    /// Whether the previous `>` closed turbofish generics
+    closed_generics: bool,
+}
+
+impl Spacer {
+    fn space_before(&mut self, out: &mut String, next: &Token) {
+        if self.needs_space(next) {
+            out.push(' ');
+        }
+    }
+
+    fn needs_space(&self, next: &Token) -> bool {
+        let prev = &self.prev;
+
+        match (prev, next) {
+            (Token::Start, _) | (Token::Open(_), _) => false,
+            // Operators made of several characters: `::`, `->`, `..=`, `+=`
+            (Token::Punct(_, Spacing::Joint), Token::Punct(..)) => false,
+            (_, Token::Punct(',' | ';' | '.' | '?', _)) => false,
+            (_, Token::Punct(':', Spacing::Joint)) => false,
+            (_, Token::Punct(':', Spacing::Alone)) => self.path,
+            (Token::Punct(':', _), _) => !self.path,
+            (Token::Punct('.', _), _) => false,
+            // Turbofish: `collect::<Vec<_>>()`
+            (Token::Punct('<', _), _) | (_, Token::Punct('<' | '>', _)) if self.generics > 0 => {
+                false
+            }
+            (Token::Punct('>', _), Token::Open(Delimiter::Parenthesis)) if self.closed_generics => {
+                false
+            }
+            // Macros: `vec![...]`
+            (Token::Ident(ident), Token::Punct('!', Spacing::Alone)) => {
+                KEYWORDS.contains(&ident.as_str())
+            }
+            (Token::Punct('#' | '\'' | '$', _), _) => false,
+            (Token::Punct(_, _), _) if self.unary => false,
+            (Token::Punct('|', _), _) if self.closure_params => false,
+            (_, Token::Punct('|', _)) if self.closure_params => false,
+            // Calls and indexing: `foo(x)`, `items[0]`
+            (Token::Ident(ident), Token::Open(Delimiter::Parenthesis | Delimiter::Bracket)) => {
+                KEYWORDS.contains(&ident.as_str())
+            }
+            (Token::Close, Token::Open(Delimiter::Parenthesis | Delimiter::Bracket)) => false,
+            _ => true,
+        }
+    }
+
+    fn after(&mut self, token: Token) {
+        if let Token::Punct(c, spacing) = &token {
+            let prev_is_operand = match &self.prev {
+                Token::Ident(ident) => !KEYWORDS.contains(&ident.as_str()),
+                Token::Atom | Token::Close => true,
+                _ => false,
+            };
+
+            self.closed_generics = false;
+            match c {
+                '<' if self.path || self.generics > 0 => self.generics += 1,
+                '>' if self.generics > 0 => {
+                    self.generics -= 1;
+                    self.closed_generics = self.generics == 0;
+                }
+                _ => {}
+            }
+
+            self.path = *c == ':' && matches!(self.prev, Token::Punct(':', Spacing::Joint));
+
+            self.unary = match c {
+                '!' => *spacing == Spacing::Alone,
+                '&' | '*' | '-' => {
+                    !prev_is_operand && !matches!(self.prev, Token::Punct(_, Spacing::Joint))
+                }
+                _ => false,
+            };
+
+            if *c == '|' && *spacing == Spacing::Alone {
+                // An opening pipe follows something that is not an operand: `map(|x| ...)`,
+                // while `||` is either an empty closure or a logical or
+                self.closure_params = !self.closure_params
+                    && !prev_is_operand
+                    && !matches!(self.prev, Token::Punct('|', Spacing::Joint));
+            }
+        } else {
+            self.unary = false;
+            self.path = false;
+            self.closed_generics = false;
+        }
+
+        self.prev = token;
+    }
+}
@@ -83,7 +83,6 @@ pub fn rewrite_rsx(input: ParseStream) -> syn::Result<ExprNode> {
         let token: TokenTree = input.step(|cursor| {
             cursor
                 .token_tree()
-                .map(|(tt, next)| (tt, next))
                 .ok_or_else(|| syn::Error::new(Span::call_site(), "unexpected end of input"))
         })?;
 
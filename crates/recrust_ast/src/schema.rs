@@ -0,0 +1,645 @@
+use crate::{
+    Diagnostic, RSXAttribute, RSXAttributes, RSXElement, RSXNode,
+    events::event_type,
+    visit::{self, Visit},
+};
+
+// ---------------------------------- Schema ----------------------------------
+
+/// Elements of the HTML living standard.
+pub const HTML_ELEMENTS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "picture",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "search",
+    "section",
+    "select",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+
+/// SVG and MathML elements that can be embedded in HTML.
+/// Their attributes are not checked.
+pub const FOREIGN_ELEMENTS: &[&str] = &[
+    // SVG
+    "svg",
+    "g",
+    "defs",
+    "symbol",
+    "use",
+    "path",
+    "rect",
+    "circle",
+    "ellipse",
+    "line",
+    "polyline",
+    "polygon",
+    "text",
+    "tspan",
+    "textPath",
+    "image",
+    "foreignObject",
+    "clipPath",
+    "mask",
+    "pattern",
+    "marker",
+    "linearGradient",
+    "radialGradient",
+    "stop",
+    "filter",
+    "feBlend",
+    "feColorMatrix",
+    "feComposite",
+    "feFlood",
+    "feGaussianBlur",
+    "feMerge",
+    "feMergeNode",
+    "feOffset",
+    "desc",
+    "animate",
+    "animateTransform",
+    "view",
+    // MathML
+    "math",
+    "mi",
+    "mn",
+    "mo",
+    "ms",
+    "mtext",
+    "mrow",
+    "mfrac",
+    "msqrt",
+    "mroot",
+    "msub",
+    "msup",
+    "msubsup",
+    "mover",
+    "munder",
+    "munderover",
+    "mtable",
+    "mtr",
+    "mtd",
+    "mspace",
+    "mstyle",
+    "semantics",
+    "annotation",
+];
+
+/// Attributes allowed on every HTML element.
+pub const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "accesskey",
+    "autocapitalize",
+    "autocorrect",
+    "autofocus",
+    "class",
+    "contenteditable",
+    "dir",
+    "draggable",
+    "enterkeyhint",
+    "hidden",
+    "id",
+    "inert",
+    "inputmode",
+    "is",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "lang",
+    "nonce",
+    "popover",
+    "role",
+    "slot",
+    "spellcheck",
+    "style",
+    "tabindex",
+    "title",
+    "translate",
+    "writingsuggestions",
+    // Attributes handled by `recrust` itself
+    "children",
+    "key",
+    "inner_html",
+];
+
+/// Attributes allowed on specific HTML elements, on top of the global ones.
+pub const ELEMENT_ATTRIBUTES: &[(&str, &[&str])] = &[
+    (
+        "a",
+        &[
+            "href",
+            "target",
+            "download",
+            "ping",
+            "rel",
+            "hreflang",
+            "type",
+            "referrerpolicy",
+        ],
+    ),
+    (
+        "area",
+        &[
+            "alt",
+            "coords",
+            "shape",
+            "href",
+            "target",
+            "download",
+            "ping",
+            "rel",
+            "referrerpolicy",
+        ],
+    ),
+    (
+        "audio",
+        &[
+            "src",
+            "crossorigin",
+            "preload",
+            "autoplay",
+            "loop",
+            "muted",
+            "controls",
+        ],
+    ),
+    ("base", &["href", "target"]),
+    ("blockquote", &["cite"]),
+    (
+        "button",
+        &[
+            "command",
+            "commandfor",
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "name",
+            "popovertarget",
+            "popovertargetaction",
+            "type",
+            "value",
+        ],
+    ),
+    ("canvas", &["width", "height"]),
+    ("col", &["span"]),
+    ("colgroup", &["span"]),
+    ("data", &["value"]),
+    ("del", &["cite", "datetime"]),
+    ("details", &["name", "open"]),
+    ("dialog", &["open"]),
+    ("embed", &["src", "type", "width", "height"]),
+    ("fieldset", &["disabled", "form", "name"]),
+    (
+        "form",
+        &[
+            "accept-charset",
+            "action",
+            "autocomplete",
+            "enctype",
+            "method",
+            "name",
+            "novalidate",
+            "rel",
+            "target",
+        ],
+    ),
+    (
+        "iframe",
+        &[
+            "src",
+            "srcdoc",
+            "name",
+            "sandbox",
+            "allow",
+            "allowfullscreen",
+            "width",
+            "height",
+            "referrerpolicy",
+            "loading",
+        ],
+    ),
+    (
+        "img",
+        &[
+            "alt",
+            "src",
+            "srcset",
+            "sizes",
+            "crossorigin",
+            "usemap",
+            "ismap",
+            "width",
+            "height",
+            "referrerpolicy",
+            "decoding",
+            "loading",
+            "fetchpriority",
+        ],
+    ),
+    (
+        "input",
+        &[
+            "accept",
+            "alpha",
+            "alt",
+            "autocomplete",
+            "checked",
+            "colorspace",
+            "dirname",
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "height",
+            "list",
+            "max",
+            "maxlength",
+            "min",
+            "minlength",
+            "multiple",
+            "name",
+            "pattern",
+            "placeholder",
+            "popovertarget",
+            "popovertargetaction",
+            "readonly",
+            "required",
+            "size",
+            "src",
+            "step",
+            "type",
+            "value",
+            "width",
+        ],
+    ),
+    ("ins", &["cite", "datetime"]),
+    ("label", &["for"]),
+    ("li", &["value"]),
+    (
+        "link",
+        &[
+            "href",
+            "crossorigin",
+            "rel",
+            "as",
+            "media",
+            "integrity",
+            "hreflang",
+            "type",
+            "referrerpolicy",
+            "sizes",
+            "imagesrcset",
+            "imagesizes",
+            "blocking",
+            "color",
+            "disabled",
+            "fetchpriority",
+        ],
+    ),
+    ("map", &["name"]),
+    (
+        "meta",
+        &["name", "http-equiv", "content", "charset", "media"],
+    ),
+    ("meter", &["value", "min", "max", "low", "high", "optimum"]),
+    (
+        "object",
+        &["data", "type", "name", "form", "width", "height"],
+    ),
+    ("ol", &["reversed", "start", "type"]),
+    ("optgroup", &["disabled", "label"]),
+    ("option", &["disabled", "label", "selected", "value"]),
+    ("output", &["for", "form", "name"]),
+    ("progress", &["value", "max"]),
+    ("q", &["cite"]),
+    (
+        "script",
+        &[
+            "src",
+            "type",
+            "nomodule",
+            "async",
+            "defer",
+            "crossorigin",
+            "integrity",
+            "referrerpolicy",
+            "blocking",
+            "fetchpriority",
+        ],
+    ),
+    (
+        "select",
+        &[
+            "autocomplete",
+            "disabled",
+            "form",
+            "multiple",
+            "name",
+            "required",
+            "size",
+        ],
+    ),
+    ("slot", &["name"]),
+    (
+        "source",
+        &["type", "media", "src", "srcset", "sizes", "width", "height"],
+    ),
+    ("style", &["media", "blocking"]),
+    ("td", &["colspan", "rowspan", "headers"]),
+    (
+        "template",
+        &[
+            "shadowrootmode",
+            "shadowrootdelegatesfocus",
+            "shadowrootclonable",
+            "shadowrootserializable",
+        ],
+    ),
+    (
+        "textarea",
+        &[
+            "autocomplete",
+            "cols",
+            "dirname",
+            "disabled",
+            "form",
+            "maxlength",
+            "minlength",
+            "name",
+            "placeholder",
+            "readonly",
+            "required",
+            "rows",
+            "wrap",
+        ],
+    ),
+    ("th", &["colspan", "rowspan", "headers", "scope", "abbr"]),
+    ("time", &["datetime"]),
+    ("track", &["default", "kind", "label", "src", "srclang"]),
+    (
+        "video",
+        &[
+            "src",
+            "crossorigin",
+            "poster",
+            "preload",
+            "autoplay",
+            "playsinline",
+            "loop",
+            "muted",
+            "controls",
+            "width",
+            "height",
+        ],
+    ),
+];
+
+/// Walks the element tree and reports the elements and attributes unknown to the HTML schema,
+/// at most one error per element.
+pub fn check_schema(node: &RSXNode) -> Vec<Diagnostic> {
+    let mut checker = Schema::default();
+    checker.visit_node(node);
+    checker.diagnostics
+}
+
+#[derive(Default)]
+struct Schema {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'ast> Visit<'ast> for Schema {
+    fn visit_element(&mut self, element: &'ast RSXElement) {
+        if let Err(err) = check_element(element) {
+            self.diagnostics
+                .push(Diagnostic::error(err.span(), err.to_string()));
+        }
+        visit::visit_element(self, element);
+    }
+}
+
+/// Checks an element and its attributes against the HTML schema.
+///
+/// Custom elements opt out of the check: their tag must contain a `-`, like `<my-widget>`, or
+/// an `_`, like `<my_widget>`, which no standard element does.
+pub fn check_element(element: &RSXElement) -> syn::Result<()> {
+    let tag = element.tag.to_string();
+
+    if tag.contains(['-', '_']) || FOREIGN_ELEMENTS.contains(&tag.as_str()) {
+        return Ok(());
+    }
+
+    if !HTML_ELEMENTS.contains(&tag.as_str()) {
+        let message = match did_you_mean(&tag, HTML_ELEMENTS.iter().copied()) {
+            Some(suggestion) => format!(
+                "unknown element `<{}>`, did you mean `<{}>`?",
+                tag, suggestion
+            ),
+            None => format!(
+                "unknown element `<{}>`; custom elements must contain a `-`, like `<my-{}>`",
+                tag, tag
+            ),
+        };
+        return Err(syn::Error::new(element.tag.span(), message));
+    }
+
+    check_attributes(&tag, &element.attributes)
+}
+
+fn check_attributes(tag: &str, attributes: &RSXAttributes) -> syn::Result<()> {
+    let element_attributes = ELEMENT_ATTRIBUTES
+        .iter()
+        .find_map(|(element, attributes)| (*element == tag).then_some(*attributes))
+        .unwrap_or_default();
+
+    for attribute in &attributes.0 {
+        let name = match attribute {
+            RSXAttribute::Normal { name, .. } | RSXAttribute::Optional { name, .. } => name,
+            _ => continue,
+        };
+        let value = name.value.as_str();
+
+        let known = GLOBAL_ATTRIBUTES.contains(&value)
+            || element_attributes.contains(&value)
+            || value.starts_with("data-")
+            || value.starts_with("aria-")
+            || value.strip_prefix("on").and_then(event_type).is_some();
+        if known {
+            continue;
+        }
+
+        let candidates = GLOBAL_ATTRIBUTES.iter().chain(element_attributes).copied();
+        let message = match did_you_mean(value, candidates) {
+            Some(suggestion) => format!(
+                "unknown attribute `{}` on `<{}>`, did you mean `{}`?",
+                value, tag, suggestion
+            ),
+            None => format!(
+                "unknown attribute `{}` on `<{}>`; custom data must use a `data-` prefix",
+                value, tag
+            ),
+        };
+        return Err(syn::Error::new(name.span, message));
+    }
+
+    Ok(())
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// Returns the closest candidate to `name`, if it is close enough to be a likely typo.
+pub(crate) fn did_you_mean<'a>(
+    name: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.len() / 3).max(1);
+
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings, where a swap of two adjacent characters counts as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // distances[i][j] = distance between a[..i] and b[..j]
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
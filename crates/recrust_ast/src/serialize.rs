@@ -0,0 +1,294 @@
+use serde::Serialize;
+
+use crate::{
+    ExprNode, PartialExpr, RSXAttribute, RSXAttributes, RSXNode, RSXTagName, SourceSpan, TagSpans,
+    print::{children, is_children},
+    print_expr,
+};
+
+// ---------------------------------- Serializable AST ----------------------------------
+
+/// Version of the serialized schema, bumped on every incompatible change.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// A serializable snapshot of a parsed node, for tools that do not link `syn`:
+/// `serde_json::to_string(&Document::new(&node))`.
+///
+/// Rust expressions are kept as source text, with the RSX nested in them listed separately.
+/// Spans cover the whole node or attribute, elements and components also have the spans of
+/// their tag name, opening tag and closing tag.
+#[derive(Clone, Debug, Serialize)]
+pub struct Document {
+    pub version: u32,
+    pub root: Node,
+}
+
+impl Document {
+    pub fn new(node: &RSXNode) -> Self {
+        Self {
+            version: SCHEMA_VERSION,
+            root: Node::new(node),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Node {
+    Element {
+        tag: String,
+        span: Location,
+        #[serde(flatten)]
+        tag_spans: TagLocations,
+        attributes: Vec<Attribute>,
+        children: Vec<Node>,
+    },
+    Component {
+        tag: String,
+        span: Location,
+        #[serde(flatten)]
+        tag_spans: TagLocations,
+        attributes: Vec<Attribute>,
+        children: Vec<Node>,
+    },
+    Text {
+        value: String,
+        span: Location,
+    },
+    RawText {
+        value: String,
+        span: Location,
+    },
+    Comment {
+        value: String,
+        span: Location,
+    },
+    Expression {
+        #[serde(flatten)]
+        expression: Expression,
+        span: Location,
+    },
+}
+
+impl Node {
+    pub fn new(node: &RSXNode) -> Self {
+        let span = Location::new(node.span());
+
+        match node {
+            RSXNode::RSXElement(element) => Node::Element {
+                tag: element.tag.to_string(),
+                span,
+                tag_spans: TagLocations::new(&element.tag, &element.spans),
+                attributes: Attribute::list(&element.attributes),
+                children: children(&element.attributes)
+                    .into_iter()
+                    .map(Node::new)
+                    .collect(),
+            },
+            RSXNode::RSXComponent(component) => Node::Component {
+                tag: component.tag.to_string(),
+                span,
+                tag_spans: TagLocations::new(&component.tag, &component.spans),
+                attributes: Attribute::list(&component.attributes),
+                children: children(&component.attributes)
+                    .into_iter()
+                    .map(Node::new)
+                    .collect(),
+            },
+            RSXNode::Text(text) => Node::Text {
+                value: text.value(),
+                span,
+            },
+            RSXNode::RawText(text) => Node::RawText {
+                value: text.value(),
+                span,
+            },
+            RSXNode::Comment(text, _) => Node::Comment {
+                value: text.value(),
+                span,
+            },
+            RSXNode::RawExpr(expr, _) => Node::Expression {
+                expression: Expression::new(expr),
+                span,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Attribute {
+    /// `name={value}`
+    Normal {
+        name: String,
+        span: Location,
+        value: Expression,
+    },
+    /// `name?={value}`
+    Optional {
+        name: String,
+        span: Location,
+        value: Expression,
+    },
+    /// `on:event={handler}`
+    Event {
+        event: String,
+        span: Location,
+        handler: Expression,
+    },
+    /// `class:name={condition}`
+    Class {
+        class: String,
+        span: Location,
+        condition: Expression,
+    },
+    /// `class={[...]}`
+    ClassList { span: Location, classes: Expression },
+    /// `style:property={value}`
+    Style {
+        property: String,
+        span: Location,
+        value: Expression,
+    },
+    /// `inner_html={value}`
+    InnerHtml { span: Location, value: Expression },
+    /// `{..source}`
+    Spread { source: String, span: Location },
+}
+
+impl Attribute {
+    /// The attributes of an element or component, without the children.
+    fn list(attributes: &RSXAttributes) -> Vec<Self> {
+        attributes
+            .0
+            .iter()
+            .filter(|attribute| !is_children(attribute))
+            .map(Attribute::new)
+            .collect()
+    }
+
+    pub fn new(attribute: &RSXAttribute) -> Self {
+        let span = Location::new(attribute.spans().full);
+
+        match attribute {
+            RSXAttribute::Normal { name, value, .. } => Attribute::Normal {
+                name: name.to_string(),
+                span,
+                value: Expression::new(value),
+            },
+            RSXAttribute::Optional { name, value, .. } => Attribute::Optional {
+                name: name.to_string(),
+                span,
+                value: Expression::new(value),
+            },
+            RSXAttribute::Event { event, handler, .. } => Attribute::Event {
+                event: event.to_string(),
+                span,
+                handler: Expression::new(handler),
+            },
+            RSXAttribute::Class {
+                class, condition, ..
+            } => Attribute::Class {
+                class: class.value(),
+                span,
+                condition: Expression::new(condition),
+            },
+            RSXAttribute::ClassList { classes, .. } => Attribute::ClassList {
+                span,
+                classes: Expression::new(classes),
+            },
+            RSXAttribute::Style {
+                property, value, ..
+            } => Attribute::Style {
+                property: property.value(),
+                span,
+                value: Expression::new(value),
+            },
+            RSXAttribute::InnerHtml { value, .. } => Attribute::InnerHtml {
+                span,
+                value: Expression::new(value),
+            },
+            RSXAttribute::Spread { ident, .. } => Attribute::Spread {
+                source: ident.to_string(),
+                span,
+            },
+        }
+    }
+}
+
+/// A Rust expression as source text, along with the RSX nested in it.
+#[derive(Clone, Debug, Serialize)]
+pub struct Expression {
+    pub source: String,
+    pub rsx: Vec<Node>,
+}
+
+impl Expression {
+    pub fn new(expr: &ExprNode) -> Self {
+        let mut rsx = Vec::new();
+        nested_rsx(expr, &mut rsx);
+
+        Self {
+            source: print_expr(expr),
+            rsx,
+        }
+    }
+}
+
+fn nested_rsx(expr: &ExprNode, rsx: &mut Vec<Node>) {
+    for part in &expr.0 {
+        match part {
+            PartialExpr::Normal(_) => {}
+            PartialExpr::RSX(node) => rsx.push(Node::new(node)),
+            PartialExpr::ExprNode { inner, .. } => nested_rsx(inner, rsx),
+        }
+    }
+}
+
+/// The spans of the parts of an element or component.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct TagLocations {
+    /// The tag name of the opening tag
+    pub tag_span: Location,
+    /// `<div ...>` or `<div ... />`
+    pub open_tag: Location,
+    /// `</div>`
+    pub close_tag: Option<Location>,
+}
+
+impl TagLocations {
+    fn new(tag: &RSXTagName, spans: &TagSpans) -> Self {
+        Self {
+            tag_span: Location::new(tag.span().into()),
+            open_tag: Location::new(spans.open),
+            close_tag: spans.close.map(Location::new),
+        }
+    }
+}
+
+/// Start and end of a span, as 1-based lines and columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Location {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub fn new(span: SourceSpan) -> Self {
+        let position = |position: proc_macro2::LineColumn| Position {
+            line: position.line,
+            column: position.column + 1,
+        };
+
+        Self {
+            start: position(span.start.start()),
+            end: position(span.end.end()),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::Display;
+
+use proc_macro2::{Punct, Spacing, Span, TokenStream, TokenTree};
+
+// ---------------------------------- Source Locations ----------------------------------
+
+/// A range of source tokens, kept as the spans of its first and last tokens.
+///
+/// `Span::join` only works on nightly inside proc macros, so both ends are kept instead of a
+/// single joined span. Errors created with [`SourceSpan::error`] still cover the whole range.
+#[derive(Clone, Copy, Debug)]
+pub struct SourceSpan {
+    pub start: Span,
+    pub end: Span,
+}
+
+impl SourceSpan {
+    pub fn new(start: Span, end: Span) -> Self {
+        Self { start, end }
+    }
+
+    /// From the start of `self` to the end of `other`.
+    pub fn to(self, other: SourceSpan) -> Self {
+        Self::new(self.start, other.end)
+    }
+
+    /// A single span covering the range when it can be joined, its start otherwise.
+    pub fn joined(self) -> Span {
+        self.start.join(self.end).unwrap_or(self.start)
+    }
+
+    /// An error underlining the whole range, like `syn::Error::new_spanned`.
+    pub fn error(self, message: impl Display) -> syn::Error {
+        let tokens = [self.start, self.end]
+            .into_iter()
+            .map(|span| {
+                let mut punct = Punct::new('<', Spacing::Alone);
+                punct.set_span(span);
+                TokenTree::Punct(punct)
+            })
+            .collect::<TokenStream>();
+
+        syn::Error::new_spanned(tokens, message)
+    }
+}
+
+impl From<Span> for SourceSpan {
+    fn from(span: Span) -> Self {
+        Self::new(span, span)
+    }
+}
+
+/// Where the parts of an element or component are: `<div ...>...</div>` or `<div ... />`.
+#[derive(Clone, Copy, Debug)]
+pub struct TagSpans {
+    /// The whole node, from the opening `<` to the end of the closing tag
+    pub full: SourceSpan,
+    /// The opening tag, `<div ...>` or `<div ... />`
+    pub open: SourceSpan,
+    /// The `<` of the opening tag
+    pub lt: Span,
+    /// The end of the opening tag, `>` or `/>`
+    pub open_end: SourceSpan,
+    /// The closing tag, `</div>`, if any
+    pub close: Option<SourceSpan>,
+}
+
+impl TagSpans {
+    /// Spans of a parsed node, from its `<`, the end of its opening tag and its closing tag.
+    pub fn new(lt: Span, open_end: SourceSpan, close: Option<SourceSpan>) -> Self {
+        let open = SourceSpan::new(lt, open_end.end);
+        Self {
+            full: close.map_or(open, |close| open.to(close)),
+            open,
+            lt,
+            open_end,
+            close,
+        }
+    }
+
+    /// Spans for a node built by hand rather than parsed.
+    pub fn call_site() -> Self {
+        Self::new(Span::call_site(), Span::call_site().into(), None)
+    }
+}
+
+/// Where an attribute is: `name={...}`, `on:click={...}` or `{..spread}`.
+#[derive(Clone, Copy, Debug)]
+pub struct AttributeSpans {
+    /// The whole attribute, from its name to the closing `}` of its value
+    pub full: SourceSpan,
+    /// The `=` between the name and the value, if any
+    pub eq: Option<Span>,
+}
+
+impl AttributeSpans {
+    /// Spans for an attribute built by hand rather than parsed.
+    pub fn call_site() -> Self {
+        Self {
+            full: SourceSpan::from(Span::call_site()),
+            eq: None,
+        }
+    }
+}
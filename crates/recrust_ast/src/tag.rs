@@ -0,0 +1,357 @@
+use std::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+};
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use syn::{
+    Ident, LitStr, Path, PathSegment, Token,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+};
+
+use crate::{
+    PartialExpr,
+    attributes::{RSXAttributes, check_inner_html},
+    classify::TagClass,
+    node::RSXNode,
+    raw_expr::ExprNode,
+    span::{SourceSpan, TagSpans},
+};
+
+// ---------------------------------- Macro Traits: Input / Output ----------------------------------
+
+/// A tag with its attributes and children: `<div ...>...</div>` or `<div ... />`.
+///
+/// Elements and components share the same syntax, parser and attribute handling. What differs
+/// between them is described by their [`TagKind`]: [`RSXElement`](crate::RSXElement) and
+/// [`RSXComponent`](crate::RSXComponent).
+#[derive(Clone, Debug)]
+pub struct RSXTag<K> {
+    pub tag: RSXTagName,
+    pub attributes: RSXAttributes,
+    /// The tag of `</div>`, none for self-closing tags and void elements
+    pub closing_tag: Option<RSXTagName>,
+    pub spans: TagSpans,
+    kind: PhantomData<K>,
+}
+
+/// What differs between intrinsic elements and components: the tags whose content is special,
+/// the checks run once a tag is parsed, and the code it expands to.
+pub trait TagKind: Sized {
+    /// How tags of this kind are classified.
+    const CLASS: TagClass;
+
+    /// Whether the tag has no content nor closing tag: `<br>`.
+    fn is_void(_tag: &str) -> bool {
+        false
+    }
+
+    /// Whether the content is always raw text, without the `raw` marker: `<style>`.
+    fn is_raw_text(_tag: &str) -> bool {
+        false
+    }
+
+    /// Checks a parsed tag, after the checks shared by every kind.
+    fn check(_node: &RSXTag<Self>) -> syn::Result<()> {
+        Ok(())
+    }
+
+    /// The expression creating the node, given the block that builds its attribute list.
+    fn expand(node: &RSXTag<Self>, attributes: TokenStream) -> TokenStream;
+}
+
+impl<K> RSXTag<K> {
+    /// A tag built by hand rather than parsed, without children or a closing tag.
+    pub fn new(tag: impl Into<RSXTagName>, attributes: RSXAttributes) -> Self {
+        Self {
+            tag: tag.into(),
+            attributes,
+            closing_tag: None,
+            spans: TagSpans::call_site(),
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<K: TagKind> Parse for RSXTag<K> {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Parse Opening Tag
+        let lt = input.parse::<Token![<]>()?.span;
+        let tag = input.parse::<RSXTagName>()?;
+        let name = tag.to_string();
+
+        // Parse attributes, and the marker opting into raw text content: `<pre raw>`
+        let (mut attributes, raw) = RSXAttributes::parse_with_raw_marker(input)?;
+        let raw = raw.is_some();
+
+        // Handle self-closing tags: `<div />`
+        if input.peek(Token![/]) && input.peek2(Token![>]) {
+            let slash = input.parse::<Token![/]>()?.span;
+            let gt = input.parse::<Token![>]>()?.span;
+            let spans = TagSpans::new(lt, SourceSpan::new(slash, gt), None);
+            return Self::parsed(tag, attributes, None, spans);
+        }
+
+        // Handle normal opening tags: `<div>`
+        let gt = input.parse::<Token![>]>()?.span;
+
+        // Handle void elements, which need no closing tag: `<br>`
+        if K::is_void(&name) {
+            check_void_element(input, &tag)?;
+            return Self::parsed(tag, attributes, None, TagSpans::new(lt, gt.into(), None));
+        }
+
+        // Parse children
+        let children_span = input.span();
+        let mut children = ExprNode(Vec::new());
+        if (raw || K::is_raw_text(&name))
+            && let Some(text) = parse_raw_text(input, &tag)?
+        {
+            children
+                .0
+                .push(PartialExpr::RSX(Box::new(RSXNode::RawText(text))));
+        }
+        while !(input.peek(Token![<]) && input.peek2(Token![/])) {
+            if input.is_empty() {
+                return Err(input.error("unexpected end of input while parsing children; expected a closing tag like `</...>`"));
+            }
+
+            children
+                .0
+                .push(PartialExpr::RSX(Box::new(input.parse::<RSXNode>()?)));
+        }
+        attributes.push_children(children, children_span)?;
+
+        // Parse Closing Tag: `</div>`
+        let close_lt = input.parse::<Token![<]>()?.span;
+        input.parse::<Token![/]>()?;
+        let closing_tag = input.parse::<RSXTagName>()?;
+        let close_gt = input.parse::<Token![>]>()?.span;
+
+        if closing_tag != tag {
+            return Err(syn::Error::new(
+                closing_tag.span(),
+                format!("mismatched closing tag: expected `</{}>`", tag),
+            ));
+        }
+
+        let close = SourceSpan::new(close_lt, close_gt);
+        Self::parsed(
+            tag,
+            attributes,
+            Some(closing_tag),
+            TagSpans::new(lt, gt.into(), Some(close)),
+        )
+    }
+}
+
+impl<K: TagKind> RSXTag<K> {
+    /// Builds a parsed tag, once it passed the checks shared by every kind and its own.
+    fn parsed(
+        tag: RSXTagName,
+        attributes: RSXAttributes,
+        closing_tag: Option<RSXTagName>,
+        spans: TagSpans,
+    ) -> syn::Result<Self> {
+        check_inner_html(&attributes)?;
+
+        let node = Self {
+            tag,
+            attributes,
+            closing_tag,
+            spans,
+            kind: PhantomData,
+        };
+        K::check(&node)?;
+        Ok(node)
+    }
+}
+
+impl<K: TagKind> ToTokens for RSXTag<K> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let attributes = &self.attributes.0;
+
+        tokens.extend(K::expand(
+            self,
+            quote! {
+                {
+                    let mut __attrs = create_attributes();
+
+                    #(#attributes)*
+
+                    __attrs
+                }
+            },
+        ));
+    }
+}
+
+/// The name of a tag: `div`, `MyComponent`, a path like `ui::Button`, a name marked as a
+/// component like `:my_component`, or a custom element name like `my-widget`.
+#[derive(Clone, Debug)]
+pub struct RSXTagName {
+    /// The `:` of `<:my_component>`, which makes any tag a component
+    pub marker: Option<Token![:]>,
+    pub path: Path,
+    /// The parts following a dash in a custom element name: `widget` in `<my-widget>`
+    pub dashed: Vec<Ident>,
+}
+
+impl RSXTagName {
+    /// Whether the name has more than one segment, or a leading `::`.
+    pub fn is_path(&self) -> bool {
+        self.path.leading_colon.is_some() || self.path.segments.len() > 1
+    }
+
+    /// Span of the first segment of the name.
+    pub fn span(&self) -> Span {
+        self.path.segments[0].ident.span()
+    }
+}
+
+impl From<Ident> for RSXTagName {
+    fn from(ident: Ident) -> Self {
+        Self {
+            marker: None,
+            path: ident.into(),
+            dashed: Vec::new(),
+        }
+    }
+}
+
+impl Parse for RSXTagName {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let marker = if input.peek(Token![:]) && !input.peek(Token![::]) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let leading_colon = input.parse::<Option<Token![::]>>()?;
+        let mut segments = Punctuated::new();
+        segments.push_value(PathSegment::from(input.call(Ident::parse_any)?));
+        while input.peek(Token![::]) {
+            segments.push_punct(input.parse()?);
+            segments.push_value(PathSegment::from(input.call(Ident::parse_any)?));
+        }
+
+        // Custom element names, which are not paths: `my-widget`
+        let mut dashed = Vec::new();
+        if marker.is_none() && leading_colon.is_none() && segments.len() == 1 {
+            while input.peek(Token![-]) && input.peek2(Ident::peek_any) {
+                input.parse::<Token![-]>()?;
+                dashed.push(input.call(Ident::parse_any)?);
+            }
+        }
+
+        Ok(Self {
+            marker,
+            path: Path {
+                leading_colon,
+                segments,
+            },
+            dashed,
+        })
+    }
+}
+
+/// The path the tag expands to, without its marker. Elements expand to their name as a string
+/// instead, see [`Intrinsic`](crate::Intrinsic).
+impl ToTokens for RSXTagName {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.path.to_tokens(tokens);
+    }
+}
+
+/// Formats the name without its marker or spaces: `ui::Button`, `my-widget`.
+impl Display for RSXTagName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.leading_colon.is_some() {
+            f.write_str("::")?;
+        }
+        for (index, segment) in self.path.segments.iter().enumerate() {
+            if index > 0 {
+                f.write_str("::")?;
+            }
+            write!(f, "{}", segment.ident)?;
+        }
+        for part in &self.dashed {
+            write!(f, "-{}", part)?;
+        }
+        Ok(())
+    }
+}
+
+/// Names are equal when their paths are, the closing tag may omit the marker: `<:a>...</a>`.
+impl PartialEq for RSXTagName {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.dashed == other.dashed
+    }
+}
+
+impl<T: AsRef<str> + ?Sized> PartialEq<T> for RSXTagName {
+    fn eq(&self, other: &T) -> bool {
+        self.to_string().as_str() == other.as_ref()
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// Makes sure a void element opened with `<br>` is not followed by children and a `</br>`.
+fn check_void_element(input: ParseStream, tag: &RSXTagName) -> syn::Result<()> {
+    // Skip token trees up to the next closing tag, without parsing what would be the children:
+    // only that closing tag can be the one of the void element, the rest is left to the parent
+    let fork = input.fork();
+    while !(fork.peek(Token![<]) && fork.peek2(Token![/])) {
+        if fork.is_empty() {
+            return Ok(());
+        }
+        fork.parse::<TokenTree>()?;
+    }
+
+    fork.parse::<Token![<]>()?;
+    fork.parse::<Token![/]>()?;
+    let Ok(closing_tag) = fork.parse::<RSXTagName>() else {
+        return Ok(());
+    };
+
+    if closing_tag == *tag {
+        return Err(syn::Error::new(
+            closing_tag.span(),
+            format!(
+                "`<{}>` is a void element and cannot have children or a closing tag; write `<{} />`",
+                tag, tag
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Parses the content of a raw text tag: nothing, or a single (raw) string literal.
+fn parse_raw_text(input: ParseStream, tag: &RSXTagName) -> syn::Result<Option<LitStr>> {
+    let at_closing_tag = |input: ParseStream| input.peek(Token![<]) && input.peek2(Token![/]);
+    if at_closing_tag(input) {
+        return Ok(None);
+    }
+
+    let error = |span| {
+        syn::Error::new(
+            span,
+            format!(
+                "`<{}>` only accepts a single string literal; nested RSX is not allowed here",
+                tag
+            ),
+        )
+    };
+
+    let text = input.parse::<LitStr>().map_err(|_| error(input.span()))?;
+    if !at_closing_tag(input) {
+        return Err(error(input.span()));
+    }
+
+    Ok(Some(text))
+}
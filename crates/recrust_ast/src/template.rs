@@ -0,0 +1,60 @@
+use std::fmt;
+
+use proc_macro2::{Span, TokenStream};
+
+use crate::RSXNode;
+
+// ---------------------------------- Templates ----------------------------------
+
+/// An error in an external template file, located by line and column in that file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplateError {
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column, in characters
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// Parses the content of an external template file, like the body of an `rsx!` invocation.
+///
+/// Inside a proc-macro, tokens parsed from a string only have the span of the macro call, so on
+/// error the template is parsed again with `proc_macro2`'s own implementation to locate it.
+pub fn parse_template(source: &str) -> Result<RSXNode, TemplateError> {
+    let parse = || -> Result<RSXNode, (Span, String)> {
+        let tokens = source
+            .parse::<TokenStream>()
+            .map_err(|err| (err.span(), err.to_string()))?;
+        syn::parse2::<RSXNode>(tokens).map_err(|err| (err.span(), err.to_string()))
+    };
+
+    let (span, message) = match parse() {
+        Ok(node) => return Ok(node),
+        Err(err) => {
+            proc_macro2::fallback::force();
+            let located = parse();
+            proc_macro2::fallback::unforce();
+            located.err().unwrap_or(err)
+        }
+    };
+    let start = span.start();
+    // Errors at the end of the input have no position of their own
+    let (line, column) = if span.source_text().is_none() {
+        let last = source.lines().last().unwrap_or_default();
+        (source.lines().count().max(1), last.chars().count() + 1)
+    } else {
+        (start.line, start.column + 1)
+    };
+
+    Err(TemplateError {
+        line,
+        column,
+        message,
+    })
+}
@@ -0,0 +1,90 @@
+// ---------------------------------- Text ----------------------------------
+
+/// Collapses whitespace like JSX: lines are trimmed, blank lines dropped and the rest joined with
+/// a space. Text that is only whitespace with a line break is dropped entirely.
+pub fn collapse_whitespace(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let last = lines.len() - 1;
+
+    let mut out = String::new();
+    for (index, mut line) in lines.into_iter().enumerate() {
+        if index > 0 {
+            line = line.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        }
+        if index < last {
+            line = line.trim_end_matches(|c: char| c.is_ascii_whitespace());
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        let mut previous_space = false;
+        for c in line.chars() {
+            let space = c.is_ascii_whitespace();
+            if !(space && previous_space) {
+                out.push(if space { ' ' } else { c });
+            }
+            previous_space = space;
+        }
+    }
+
+    (!out.is_empty()).then_some(out)
+}
+
+/// Decodes character references: `&amp;`, `&#169;`, `&#xA9;` and a few common named ones.
+pub fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                "copy" => Some('©'),
+                "reg" => Some('®'),
+                "trade" => Some('™'),
+                "hellip" => Some('…'),
+                "mdash" => Some('—'),
+                "ndash" => Some('–'),
+                "laquo" => Some('«'),
+                "raquo" => Some('»'),
+                "middot" => Some('·'),
+                "times" => Some('×'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse::<u32>))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            }?;
+            Some((decoded, end))
+        });
+
+        match decoded {
+            Some((decoded, end)) => {
+                out.push(decoded);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
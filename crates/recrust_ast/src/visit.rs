@@ -0,0 +1,93 @@
+use syn::LitStr;
+
+use crate::{
+    ExprNode, PartialExpr, RSXAttribute, RSXAttributes, RSXComponent, RSXElement, RSXNode,
+};
+
+// ---------------------------------- Visit ----------------------------------
+
+/// Read-only traversal of the RSX AST, in the style of `syn::visit`.
+///
+/// Each method defaults to the free function of the same name, which visits the children of the
+/// node. Override a method to inspect a node, and call the free function from it to keep walking
+/// down. Nested RSX inside attribute values and `{ ... }` expressions is visited as well.
+pub trait Visit<'ast> {
+    fn visit_node(&mut self, node: &'ast RSXNode) {
+        visit_node(self, node)
+    }
+
+    fn visit_element(&mut self, element: &'ast RSXElement) {
+        visit_element(self, element)
+    }
+
+    fn visit_component(&mut self, component: &'ast RSXComponent) {
+        visit_component(self, component)
+    }
+
+    fn visit_attributes(&mut self, attributes: &'ast RSXAttributes) {
+        visit_attributes(self, attributes)
+    }
+
+    fn visit_attribute(&mut self, attribute: &'ast RSXAttribute) {
+        visit_attribute(self, attribute)
+    }
+
+    fn visit_expr_node(&mut self, expr: &'ast ExprNode) {
+        visit_expr_node(self, expr)
+    }
+
+    fn visit_partial_expr(&mut self, part: &'ast PartialExpr) {
+        visit_partial_expr(self, part)
+    }
+
+    fn visit_text(&mut self, _text: &'ast LitStr) {}
+
+    fn visit_raw_text(&mut self, _text: &'ast LitStr) {}
+
+    fn visit_comment(&mut self, _text: &'ast LitStr) {}
+}
+
+pub fn visit_node<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast RSXNode) {
+    match node {
+        RSXNode::RSXElement(element) => v.visit_element(element),
+        RSXNode::RSXComponent(component) => v.visit_component(component),
+        RSXNode::Text(text) => v.visit_text(text),
+        RSXNode::RawText(text) => v.visit_raw_text(text),
+        RSXNode::Comment(text, _) => v.visit_comment(text),
+        RSXNode::RawExpr(expr, _) => v.visit_expr_node(expr),
+    }
+}
+
+pub fn visit_element<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, element: &'ast RSXElement) {
+    v.visit_attributes(&element.attributes);
+}
+
+pub fn visit_component<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, component: &'ast RSXComponent) {
+    v.visit_attributes(&component.attributes);
+}
+
+pub fn visit_attributes<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, attributes: &'ast RSXAttributes) {
+    for attribute in &attributes.0 {
+        v.visit_attribute(attribute);
+    }
+}
+
+pub fn visit_attribute<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, attribute: &'ast RSXAttribute) {
+    if let Some(value) = attribute.value() {
+        v.visit_expr_node(value);
+    }
+}
+
+pub fn visit_expr_node<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, expr: &'ast ExprNode) {
+    for part in &expr.0 {
+        v.visit_partial_expr(part);
+    }
+}
+
+pub fn visit_partial_expr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, part: &'ast PartialExpr) {
+    match part {
+        PartialExpr::Normal(_) => {}
+        PartialExpr::RSX(node) => v.visit_node(node),
+        PartialExpr::ExprNode { inner, .. } => v.visit_expr_node(inner),
+    }
+}
@@ -0,0 +1,92 @@
+use syn::LitStr;
+
+use crate::{
+    ExprNode, PartialExpr, RSXAttribute, RSXAttributes, RSXComponent, RSXElement, RSXNode,
+};
+
+// ---------------------------------- VisitMut ----------------------------------
+
+/// In-place traversal of the RSX AST, in the style of `syn::visit_mut`.
+///
+/// Works like [`Visit`](crate::visit::Visit), but hands out mutable references so nodes can be
+/// rewritten while walking the tree.
+pub trait VisitMut {
+    fn visit_node_mut(&mut self, node: &mut RSXNode) {
+        visit_node_mut(self, node)
+    }
+
+    fn visit_element_mut(&mut self, element: &mut RSXElement) {
+        visit_element_mut(self, element)
+    }
+
+    fn visit_component_mut(&mut self, component: &mut RSXComponent) {
+        visit_component_mut(self, component)
+    }
+
+    fn visit_attributes_mut(&mut self, attributes: &mut RSXAttributes) {
+        visit_attributes_mut(self, attributes)
+    }
+
+    fn visit_attribute_mut(&mut self, attribute: &mut RSXAttribute) {
+        visit_attribute_mut(self, attribute)
+    }
+
+    fn visit_expr_node_mut(&mut self, expr: &mut ExprNode) {
+        visit_expr_node_mut(self, expr)
+    }
+
+    fn visit_partial_expr_mut(&mut self, part: &mut PartialExpr) {
+        visit_partial_expr_mut(self, part)
+    }
+
+    fn visit_text_mut(&mut self, _text: &mut LitStr) {}
+
+    fn visit_raw_text_mut(&mut self, _text: &mut LitStr) {}
+
+    fn visit_comment_mut(&mut self, _text: &mut LitStr) {}
+}
+
+pub fn visit_node_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut RSXNode) {
+    match node {
+        RSXNode::RSXElement(element) => v.visit_element_mut(element),
+        RSXNode::RSXComponent(component) => v.visit_component_mut(component),
+        RSXNode::Text(text) => v.visit_text_mut(text),
+        RSXNode::RawText(text) => v.visit_raw_text_mut(text),
+        RSXNode::Comment(text, _) => v.visit_comment_mut(text),
+        RSXNode::RawExpr(expr, _) => v.visit_expr_node_mut(expr),
+    }
+}
+
+pub fn visit_element_mut<V: VisitMut + ?Sized>(v: &mut V, element: &mut RSXElement) {
+    v.visit_attributes_mut(&mut element.attributes);
+}
+
+pub fn visit_component_mut<V: VisitMut + ?Sized>(v: &mut V, component: &mut RSXComponent) {
+    v.visit_attributes_mut(&mut component.attributes);
+}
+
+pub fn visit_attributes_mut<V: VisitMut + ?Sized>(v: &mut V, attributes: &mut RSXAttributes) {
+    for attribute in &mut attributes.0 {
+        v.visit_attribute_mut(attribute);
+    }
+}
+
+pub fn visit_attribute_mut<V: VisitMut + ?Sized>(v: &mut V, attribute: &mut RSXAttribute) {
+    if let Some(value) = attribute.value_mut() {
+        v.visit_expr_node_mut(value);
+    }
+}
+
+pub fn visit_expr_node_mut<V: VisitMut + ?Sized>(v: &mut V, expr: &mut ExprNode) {
+    for part in &mut expr.0 {
+        v.visit_partial_expr_mut(part);
+    }
+}
+
+pub fn visit_partial_expr_mut<V: VisitMut + ?Sized>(v: &mut V, part: &mut PartialExpr) {
+    match part {
+        PartialExpr::Normal(_) => {}
+        PartialExpr::RSX(node) => v.visit_node_mut(node),
+        PartialExpr::ExprNode { inner, .. } => v.visit_expr_node_mut(inner),
+    }
+}
@@ -0,0 +1,89 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[allow(dead_code)]
+mod utils;
+use recrust_ast::{Level, check_accessibility};
+use utils::parse_node;
+
+fn check(tokens: TokenStream) -> Vec<String> {
+    check_accessibility(&parse_node(tokens), Level::Warning)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn accessible_markup() {
+    assert!(
+        check(quote!(
+            <form>
+                <img src={logo} alt={"Company logo"} />
+                <label>"Name" <input type={"text"} /></label>
+                <input id={"email"} />
+                <input type={"submit"} />
+                <button aria-label={"Close"} on:click={close} />
+                <div role={"button"} tabindex={0} on:click={toggle}>"Toggle"</div>
+                <span aria-hidden={"true"}>"*"</span>
+            </form>
+        ))
+        .is_empty()
+    );
+}
+
+#[test]
+fn img_without_alt() {
+    assert_eq!(
+        check(quote!(<img src={src} />)),
+        ["`<img>` is missing an `alt` attribute; use `alt={\"\"}` for decorative images"]
+    );
+    // The alt might come from the spread
+    assert!(check(quote!(<img src={src} {..rest} />)).is_empty());
+}
+
+#[test]
+fn button_without_name() {
+    assert_eq!(
+        check(quote!(<button on:click={close} />)),
+        ["`<button>` has no accessible name; add text content or an `aria-label`"]
+    );
+    assert!(check(quote!(<button>"Close"</button>)).is_empty());
+}
+
+#[test]
+fn invalid_aria() {
+    assert_eq!(
+        check(quote!(<div aria-lable={"Menu"} role={"buton navigation"} />)),
+        [
+            "unknown ARIA attribute `aria-lable`, did you mean `aria-label`?",
+            "unknown ARIA role `buton`, did you mean `button`?",
+        ]
+    );
+}
+
+#[test]
+fn click_on_non_interactive() {
+    assert_eq!(
+        check(quote!(<div on:click={open}>"Open"</div>)),
+        [
+            "`on:click` on the non-interactive `<div>`; use a `<button>`, or add a `role`, a `tabindex` and a keyboard handler"
+        ]
+    );
+}
+
+#[test]
+fn input_without_label() {
+    assert_eq!(
+        check(quote!(<div><textarea placeholder={"Message"} /></div>)),
+        [
+            "`<textarea>` has no label; wrap it in a `<label>`, give it an `id` referenced by a `<label for=...>` or add an `aria-label`"
+        ]
+    );
+}
+
+#[test]
+fn escalated_to_errors() {
+    let diagnostics = check_accessibility(&parse_node(quote!(<img />)), Level::Error);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].level, Level::Error);
+}
@@ -0,0 +1,199 @@
+use quote::{ToTokens, quote};
+
+#[allow(dead_code)]
+mod utils;
+use recrust_ast::{RSXAttribute, RSXNode};
+use utils::parse_element;
+
+#[test]
+fn event_handler() {
+    let el = parse_element(quote!(<button on:click={|_| count += 1} />));
+
+    assert_eq!(el.attributes.0.len(), 1);
+    assert!(matches!(
+        &el.attributes.0[0],
+        RSXAttribute::Event { event, handler, .. }
+            if event == "click" && handler.to_token_stream().to_string() == "| _ | count += 1"
+    ));
+
+    // ToTokens should register a listener typed with the event payload
+    let tokens = el.to_token_stream().to_string();
+    assert!(tokens.contains("\"on:click\""));
+    assert!(tokens.contains("create_listener :: < MouseEvent >"));
+}
+
+#[test]
+fn event_payload_types() {
+    let el = parse_element(quote!(<input on:keydown={on_key} on:input={on_input} />));
+
+    let tokens = el.to_token_stream().to_string();
+    assert!(tokens.contains("create_listener :: < KeyboardEvent > (on_key)"));
+    assert!(tokens.contains("create_listener :: < InputEvent > (on_input)"));
+}
+
+#[test]
+fn unknown_event() {
+    let err = syn::parse2::<RSXNode>(quote!(<button on:clik={handler} />))
+        .expect_err("unknown events should be rejected");
+
+    assert_eq!(err.to_string(), "unknown event `clik`");
+}
+
+#[test]
+fn unknown_directive() {
+    let err = syn::parse2::<RSXNode>(quote!(<button bind:value={value} />))
+        .expect_err("unknown directives should be rejected");
+
+    assert!(
+        err.to_string()
+            .starts_with("unknown attribute directive `bind:`")
+    );
+}
+
+#[test]
+fn class_directive() {
+    let el = parse_element(quote!(<div class:active={is_active} class:btn-primary={primary} />));
+
+    assert_eq!(el.attributes.0.len(), 2);
+    assert!(matches!(
+        &el.attributes.0[0],
+        RSXAttribute::Class { class, condition, .. }
+            if class.value() == "active" && condition.to_token_stream().to_string() == "is_active"
+    ));
+    assert!(matches!(
+        &el.attributes.0[1],
+        RSXAttribute::Class { class, .. } if class.value() == "btn-primary"
+    ));
+
+    // ToTokens should push a `class` entry only when the condition holds
+    let tokens = el.to_token_stream().to_string();
+    assert!(tokens.contains("if { is_active } { __attrs . push ((\"class\" , \"active\")) ; }"));
+}
+
+#[test]
+fn class_list() {
+    let el = parse_element(quote!(
+        <div class={["btn", if primary { "btn-primary" } else { "" }]} {..extra} />
+    ));
+
+    assert_eq!(el.attributes.0.len(), 2);
+    let RSXAttribute::ClassList { classes, .. } = &el.attributes.0[0] else {
+        panic!("expected class list, got {:?}", el.attributes.0[0]);
+    };
+    assert!(
+        classes
+            .to_token_stream()
+            .to_string()
+            .starts_with("\"btn\" ,")
+    );
+
+    // Every class is pushed as its own `class` entry, next to the spread ones
+    let tokens = el.to_token_stream().to_string();
+    assert!(tokens.contains("for __class in [\"btn\" ,"));
+    assert!(tokens.contains("__attrs . push ((\"class\" , __class))"));
+    assert!(tokens.contains("__attrs . extend (extra)"));
+}
+
+#[test]
+fn class_string_stays_normal() {
+    let el = parse_element(quote!(<div class={"btn btn-primary"} />));
+
+    assert!(matches!(
+        &el.attributes.0[0],
+        RSXAttribute::Normal { name, .. } if name == "class"
+    ));
+}
+
+#[test]
+fn style_directive() {
+    let el = parse_element(quote!(
+        <div style={"color: red"} style:width={px(20)} style:background-color={color} />
+    ));
+
+    assert_eq!(el.attributes.0.len(), 3);
+    assert!(matches!(
+        &el.attributes.0[0],
+        RSXAttribute::Normal { name, .. } if name == "style"
+    ));
+    assert!(matches!(
+        &el.attributes.0[1],
+        RSXAttribute::Style { property, value, .. }
+            if property.value() == "width" && value.to_token_stream().to_string() == "px (20)"
+    ));
+    assert!(matches!(
+        &el.attributes.0[2],
+        RSXAttribute::Style { property, .. } if property.value() == "background-color"
+    ));
+
+    // ToTokens should push each property as its own `style:` entry
+    let tokens = el.to_token_stream().to_string();
+    assert!(tokens.contains("__attrs . push ((\"style\" , \"color: red\"))"));
+    assert!(tokens.contains("__attrs . push ((\"style:width\" , px (20)))"));
+    assert!(tokens.contains("__attrs . push ((\"style:background-color\" , color))"));
+}
+
+#[test]
+fn trusted_inner_html() {
+    let el = parse_element(quote!(<article inner_html={Trusted::new(html)} />));
+
+    assert!(matches!(
+        &el.attributes.0[0],
+        RSXAttribute::InnerHtml { value, .. }
+            if value.to_token_stream().to_string() == "Trusted :: new (html)"
+    ));
+
+    // ToTokens should require the value to be the `Trusted` marker type
+    let tokens = el.to_token_stream().to_string();
+    assert!(tokens.contains("let __html : Trusted = { Trusted :: new (html) } ;"));
+    assert!(tokens.contains("create_inner_html (__html)"));
+}
+
+#[test]
+fn inner_html_with_children() {
+    let err = syn::parse2::<RSXNode>(quote!(<div inner_html={html}><span /></div>))
+        .expect_err("inner_html and children cannot be combined");
+    assert_eq!(
+        err.to_string(),
+        "`inner_html` cannot be used on an element that also has children"
+    );
+
+    syn::parse2::<RSXNode>(quote!(<div children={x} inner_html={html} />))
+        .expect_err("inner_html and an explicit children attribute cannot be combined");
+    syn::parse2::<RSXNode>(quote!(<Card inner_html={html}>"text"</Card>))
+        .expect_err("components are checked as well");
+}
+
+#[test]
+fn duplicate_attributes() {
+    let err = syn::parse2::<RSXNode>(quote!(<div id={a} id={b} />))
+        .expect_err("duplicate attributes should be rejected");
+
+    // Both occurrences are reported
+    let messages: Vec<_> = err.into_iter().map(|err| err.to_string()).collect();
+    assert_eq!(
+        messages,
+        ["duplicate attribute `id`", "`id` is first set here"]
+    );
+
+    syn::parse2::<RSXNode>(quote!(<div title?={a} title={b} />))
+        .expect_err("optional attributes count as well");
+    syn::parse2::<RSXNode>(quote!(<div on:click={a} on:click={b} />))
+        .expect_err("directives count as well");
+}
+
+#[test]
+fn duplicate_children() {
+    let err = syn::parse2::<RSXNode>(quote!(<div children={x}><span /></div>))
+        .expect_err("explicit and nested children should be rejected");
+
+    assert_eq!(err.to_string(), "duplicate attribute `children`");
+}
+
+#[test]
+fn spread_and_class_merging_are_not_duplicates() {
+    let el = parse_element(quote!(
+        <div {..defaults} id={"main"} class={"btn"} class={["big"]} class:active={on} {..overrides} />
+    ));
+
+    assert_eq!(el.attributes.0.len(), 6);
+}
@@ -0,0 +1,99 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use recrust_ast::{
+    PrintOptions, RSXAttributes, RSXComponent, RSXElement, RSXNode, TagClass, print_rsx,
+    with_classifier,
+};
+
+fn parse(tokens: TokenStream) -> RSXNode {
+    syn::parse2(tokens).expect("failed to parse Node")
+}
+
+fn is_component(node: &RSXNode) -> bool {
+    matches!(node, RSXNode::RSXComponent(_))
+}
+
+#[test]
+fn default_classification() {
+    assert!(!is_component(&parse(quote!(<div />))));
+    assert!(is_component(&parse(quote!(<Card />))));
+    assert!(is_component(&parse(quote!(<_private />))));
+}
+
+#[test]
+fn paths_are_components() {
+    let node = parse(quote!(<ui::button kind={"primary"}>"Save"</ui::button>));
+    let RSXNode::RSXComponent(component) = &node else {
+        panic!("expected a component");
+    };
+    assert_eq!(component.tag, "ui::button");
+    assert_eq!(
+        parse(quote!(<ui::Button />)).to_token_stream().to_string(),
+        quote!(create_element(ui::Button, {
+            let mut __attrs = create_attributes();
+            __attrs
+        }))
+        .to_string()
+    );
+    assert!(is_component(&parse(quote!(<::app::Card />))));
+    assert!(is_component(&parse(quote!(<self::card />))));
+}
+
+#[test]
+fn marked_tags_are_components() {
+    let node = parse(quote!(<:card title={"Hi"}>"Body"</:card>));
+    assert!(is_component(&node));
+    assert_eq!(
+        print_rsx(&node, &PrintOptions::default()),
+        r#"<:card title={"Hi"}>"Body"</:card>"#
+    );
+
+    // The closing tag may omit the marker
+    assert!(is_component(&parse(quote!(<:card>"Body"</card>))));
+
+    let err = syn::parse2::<RSXElement>(quote!(<:card />)).unwrap_err();
+    assert_eq!(err.to_string(), "`<card>` is a component, not an element");
+}
+
+#[test]
+fn components_are_printed_so_they_parse_back() {
+    let component = RSXComponent::new(
+        syn::Ident::new("card", proc_macro2::Span::call_site()),
+        RSXAttributes(Vec::new()),
+    );
+    let printed = print_rsx(&RSXNode::RSXComponent(component), &PrintOptions::default());
+
+    assert_eq!(printed, "<:card />");
+    assert!(is_component(&syn::parse_str::<RSXNode>(&printed).unwrap()));
+}
+
+#[test]
+fn custom_classifier() {
+    let classifier = |tag: &str| {
+        if tag.starts_with("x_") {
+            TagClass::Component
+        } else {
+            recrust_ast::default_classifier(tag)
+        }
+    };
+
+    let node = with_classifier(classifier, || {
+        parse(quote!(<div>{ items.map(|item| <x_row item={item} />) }</div>))
+    });
+    let printed = print_rsx(&node, &PrintOptions::default());
+    assert_eq!(
+        printed,
+        "<div>{items.map(|item| <:x_row item={item} />)}</div>"
+    );
+
+    // Paths and markers are not up to the classifier
+    let all_elements = |_: &str| TagClass::Element;
+    with_classifier(all_elements, || {
+        assert!(!is_component(&parse(quote!(<Card />))));
+        assert!(is_component(&parse(quote!(<ui::Card />))));
+        assert!(is_component(&parse(quote!(<:card />))));
+    });
+
+    // The previous classifier is restored afterwards
+    assert!(!is_component(&parse(quote!(<x_row />))));
+}
@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[allow(dead_code)]
+mod utils;
+use recrust_ast::{Level, check_content_model};
+use utils::parse_node;
+
+fn check(tokens: TokenStream) -> Vec<(Level, String)> {
+    check_content_model(&parse_node(tokens))
+        .into_iter()
+        .map(|diagnostic| (diagnostic.level, diagnostic.message))
+        .collect()
+}
+
+#[test]
+fn valid_nesting() {
+    assert!(
+        check(quote!(
+            <table>
+                <tbody>
+                    <tr><td>"cell"</td></tr>
+                </tbody>
+            </table>
+        ))
+        .is_empty()
+    );
+    assert!(check(quote!(<p><span><a href={url}>"link"</a></span></p>)).is_empty());
+    assert!(check(quote!(<ul>{items.iter().map(|item| <li>{item}</li>)}</ul>)).is_empty());
+}
+
+#[test]
+fn block_in_paragraph() {
+    assert_eq!(
+        check(quote!(<p><span><div /></span></p>)),
+        [(
+            Level::Error,
+            "`<div>` cannot be inside a `<p>`; browsers close the `<p>` before it".to_string()
+        )]
+    );
+}
+
+#[test]
+fn interactive_in_interactive() {
+    assert_eq!(
+        check(quote!(<a href={url}><a href={other} /></a>)),
+        [(
+            Level::Error,
+            "interactive element `<a>` cannot be inside a `<a>`".to_string()
+        )]
+    );
+    assert_eq!(
+        check(quote!(<button><input /></button>)),
+        [(
+            Level::Warning,
+            "interactive element `<input>` cannot be inside a `<button>`".to_string()
+        )]
+    );
+}
+
+#[test]
+fn required_parents() {
+    assert_eq!(
+        check(quote!(<table><tr /></table>)),
+        [(
+            Level::Error,
+            "`<tr>` cannot be a direct child of `<table>`; browsers wrap it in a `<tbody>`, add it explicitly"
+                .to_string()
+        )]
+    );
+    assert_eq!(
+        check(quote!(<div>{items.iter().map(|item| <li>{item}</li>)}</div>)),
+        [(
+            Level::Warning,
+            "`<li>` must be a direct child of `<ul>`, `<ol>`, `<menu>`, not `<div>`".to_string()
+        )]
+    );
+}
+
+#[test]
+fn unknown_parents_are_not_checked() {
+    // Roots, children of components and RSX in other attributes can end up anywhere
+    assert!(check(quote!(<li />)).is_empty());
+    assert!(check(quote!(<List><li /></List>)).is_empty());
+    assert!(check(quote!(<p slot={<div />} />)).is_empty());
+}
@@ -62,7 +62,7 @@ fn simple_braced() {
     };
 
     match node.as_ref() {
-        RSXNode::RawExpr(expr) => assert_eq!(expr.to_token_stream().to_string(), "count"),
+        RSXNode::RawExpr(expr, _) => assert_eq!(expr.to_token_stream().to_string(), "count"),
         other => panic!(
             "expected `Node::RawExpr`, got {:?}",
             std::mem::discriminant(other)
@@ -0,0 +1,217 @@
+use recrust_ast::{FormatOptions, format_source, line_column};
+
+fn format(source: &str) -> String {
+    let formatted = format_source(source, &FormatOptions::default());
+    assert!(
+        formatted.diagnostics.is_empty(),
+        "{:?}",
+        formatted.diagnostics
+    );
+    formatted.output
+}
+
+#[test]
+fn canonical_indentation() {
+    let source = r#"
+fn view() -> Node {
+    rsx! {<ul class={"list"}>
+  <li>"one"</li>
+            <li>{count+1}</li></ul>}
+}
+"#;
+
+    assert_eq!(
+        format(source),
+        r#"
+fn view() -> Node {
+    rsx! {
+        <ul class={"list"}>
+            <li>"one"</li>
+            <li>{count + 1}</li>
+        </ul>
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn short_blocks_stay_on_one_line() {
+    assert_eq!(
+        format("let a = rsx!{<br/>};\nlet b = rsx!(<Icon   name={\"x\"}/>);"),
+        "let a = rsx! { <br /> };\nlet b = rsx!(<Icon name={\"x\"} />);"
+    );
+}
+
+#[test]
+fn wraps_attributes_by_width() {
+    let source = r#"fn view() {
+    rsx! { <input type={"email"} name={"email"} placeholder={"you@example.com"} required={true} /> }
+}"#;
+    let options = FormatOptions {
+        max_width: 60,
+        ..FormatOptions::default()
+    };
+
+    assert_eq!(
+        format_source(source, &options).output,
+        r#"fn view() {
+    rsx! {
+        <input
+            type={"email"}
+            name={"email"}
+            placeholder={"you@example.com"}
+            required={true}
+        />
+    }
+}"#
+    );
+}
+
+#[test]
+fn preserves_comments_in_front_of_children() {
+    let source = r#"
+rsx! {
+    // The whole page
+    <main>
+        // Navigation ("//" inside strings is not a comment)
+        <nav>"a // b"</nav>
+        /* Disabled for now:
+         * <Footer />
+         */
+        {footer}
+    </main>
+}
+"#;
+
+    assert_eq!(format(source), source);
+}
+
+#[test]
+fn round_trips_html_comments() {
+    assert_eq!(
+        format("rsx! { <div><!-- TODO: remove this, it's old --><span/></div> }"),
+        "rsx! {\n    <div>\n        <!-- TODO: remove this, it's old -->\n        <span />\n    </div>\n}"
+    );
+    // Literals are kept as written
+    assert_eq!(
+        format(r##"rsx! { <p><!-- r#"a "quoted" note"# -->"text"</p> }"##),
+        r##"rsx! {
+    <p>
+        <!-- r#"a "quoted" note"# -->
+        "text"
+    </p>
+}"##
+    );
+}
+
+#[test]
+fn leaves_unsupported_comments_alone() {
+    let source = "rsx! { <div id={a /* note */} /> }";
+    let formatted = format_source(source, &FormatOptions::default());
+
+    assert_eq!(formatted.output, source);
+    assert_eq!(formatted.diagnostics.len(), 1);
+    assert!(
+        formatted.diagnostics[0]
+            .message
+            .contains("only comments in front of a child")
+    );
+}
+
+#[test]
+fn reports_blocks_that_do_not_parse() {
+    let source = "fn a() {}\nfn view() { rsx! { <div></span> } }";
+    let formatted = format_source(source, &FormatOptions::default());
+
+    assert_eq!(formatted.output, source);
+    assert_eq!(formatted.diagnostics.len(), 1);
+    assert_eq!(line_column(formatted.diagnostics[0].span), (2, 27));
+}
+
+#[test]
+fn formatting_is_idempotent() {
+    let source = r#"
+fn view() {
+    let items = vec![rsx!{<li>"é"</li>}, rsx! { <li on:click={move |_| select(&item)} class:active={active} data-id={item.id}>{&item.name}</li> }];
+}
+"#;
+    let once = format(source);
+
+    assert_eq!(format(&once), once);
+    assert!(once.contains("vec![rsx! { <li>\"é\"</li> }, rsx! {\n"));
+}
+
+#[test]
+fn keeps_multiline_expressions_as_written() {
+    let source = r#"
+fn view() -> Node {
+    rsx! {
+      <ul on:click={move |event| {
+          log(event);
+          select(event.target());
+      }}>
+          {items
+              .iter()
+              .map(|item| <li>{item}</li>)
+              .collect::<Vec<_>>()}
+      </ul>
+    }
+}
+"#;
+
+    assert_eq!(
+        format(source),
+        r#"
+fn view() -> Node {
+    rsx! {
+        <ul
+            on:click={move |event| {
+                log(event);
+                select(event.target());
+            }}
+        >
+            {items
+                .iter()
+                .map(|item| <li>{item}</li>)
+                .collect::<Vec<_>>()}
+        </ul>
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn lines_fit_in_max_width() {
+    let options = FormatOptions::default();
+    let source = r#"
+fn view() {
+    rsx! { <p><span class={"label"}>{format!("{} of {} items selected, {} remaining", selected, total, total - selected)}</span></p> }
+}
+"#;
+    let formatted = format_source(source, &options);
+    assert_eq!(formatted.output, source);
+    assert!(
+        formatted.diagnostics[0]
+            .message
+            .contains("wider than 100 columns")
+    );
+
+    // Blocks are either formatted within the width, or left as they are
+    for source in [
+        include_str!("../../recrust_runtime/tests/dom.rs"),
+        include_str!("../../recrust_runtime/tests/hooks.rs"),
+        include_str!("../../recrust_runtime/tests/rsx.rs"),
+        include_str!("../../recrust_runtime/tests/ssr.rs"),
+    ] {
+        let formatted = format_source(source, &options);
+        for line in formatted.output.lines() {
+            assert!(
+                line.chars().count() <= options.max_width || source.lines().any(|l| l == line),
+                "{}",
+                line
+            );
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use quote::{ToTokens, quote};
 
 mod utils;
-use recrust_ast::{PartialExpr, RSXAttribute};
+use recrust_ast::{PartialExpr, RSXAttribute, RSXNode};
 use utils::parse_element;
 
 use crate::utils::{expect_element, prop_tokens};
@@ -31,13 +31,13 @@ fn tag_with_children_and_attributes() {
 
     assert_eq!(el.tag.to_string(), "div");
     assert!(el.attributes.0.iter().any(
-        |attr| matches!(attr, RSXAttribute::Normal { name, value } if name.to_string() == "id")
+        |attr| matches!(attr, RSXAttribute::Normal { name, value, .. } if name.to_string() == "id")
     ));
     assert!(
         el.attributes
             .0
             .iter()
-            .any(|attr| matches!(attr, RSXAttribute::Normal { name, value } if name.to_string() == "children"))
+            .any(|attr| matches!(attr, RSXAttribute::Normal { name, value, .. } if name.to_string() == "children"))
     );
 
     // prop value is stored as `Braced` (contents of `{ ... }`)
@@ -71,7 +71,7 @@ fn spread_attributes() {
     assert_eq!(el.attributes.0.len(), 1);
     assert!(matches!(
         &el.attributes.0[0],
-        RSXAttribute::Spread { ident } if ident.to_string() == "attrs"
+        RSXAttribute::Spread { ident, .. } if ident.to_string() == "attrs"
     ));
 
     // ToTokens should emit __attrs.extend(attrs)
@@ -98,6 +98,204 @@ fn mix_normal_and_spread_attributes() {
     // Second: Spread
     assert!(matches!(
         &el.attributes.0[1],
-        RSXAttribute::Spread { ident } if ident.to_string() == "extra"
+        RSXAttribute::Spread { ident, .. } if ident.to_string() == "extra"
     ));
 }
+
+#[test]
+fn optional_attribute() {
+    let node = quote!(<div title?={maybe_title} />);
+    let el = parse_element(node);
+
+    assert_eq!(el.attributes.0.len(), 1);
+    assert!(matches!(
+        &el.attributes.0[0],
+        RSXAttribute::Optional { name, value, .. }
+            if name == "title" && value.to_token_stream().to_string() == "maybe_title"
+    ));
+
+    // ToTokens should only push the attribute when the value is `Some`
+    let tokens = el.to_token_stream().to_string();
+    assert!(tokens.contains("if let :: core :: option :: Option :: Some (__value)"));
+    assert!(tokens.contains("__attrs . push ((\"title\" , __value))"));
+}
+
+#[test]
+fn comments() {
+    let el = parse_element(quote!(
+        <div>
+            <!-- "hydration marker" -->
+            <!-- TODO: add the footer -->
+            <span />
+        </div>
+    ));
+
+    let children = &prop_tokens(&el, "children").0;
+    assert_eq!(children.len(), 3);
+
+    let comments: Vec<_> = children
+        .iter()
+        .filter_map(|child| match child {
+            PartialExpr::RSX(node) => match node.as_ref() {
+                RSXNode::Comment(text, _) => Some(text.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(comments, ["hydration marker", "TODO : add the footer"]);
+
+    // ToTokens should hand the comment text to the backend
+    let tokens = el.to_token_stream().to_string();
+    assert!(tokens.contains("create_comment (\"hydration marker\")"));
+}
+
+#[test]
+fn comments_keep_their_source_text() {
+    let node = syn::parse_str::<RSXNode>("<!-- TODO: remove this, it's old (v1.2) -->").unwrap();
+    let RSXNode::Comment(text, _) = &node else {
+        panic!("expected a comment");
+    };
+    assert_eq!(text.value(), "TODO: remove this, it's old (v1.2)");
+    assert!(
+        node.to_token_stream()
+            .to_string()
+            .contains("\"TODO: remove this, it's old (v1.2)\"")
+    );
+}
+
+#[test]
+fn unterminated_comment() {
+    let err = syn::parse2::<RSXNode>(quote!(<div><!-- "oops" </div>))
+        .expect_err("comments must be closed");
+
+    assert_eq!(
+        err.to_string(),
+        "unterminated comment; expected a closing `-->`"
+    );
+}
+
+#[test]
+fn raw_text_elements() {
+    let el = parse_element(quote!(<style>r#"p > a { color: red; }"#</style>));
+
+    let children = &prop_tokens(&el, "children").0;
+    assert_eq!(children.len(), 1);
+    assert!(matches!(
+        &children[0],
+        PartialExpr::RSX(node)
+            if matches!(node.as_ref(), RSXNode::RawText(text) if text.value() == "p > a { color: red; }")
+    ));
+
+    // ToTokens should hand the text to the backend to be emitted unescaped
+    let tokens = el.to_token_stream().to_string();
+    assert!(tokens.contains("create_raw_text (r#\"p > a { color: red; }\"#)"));
+
+    // Empty raw text elements have no children
+    let el = parse_element(quote!(<script src={"app.js"}></script>));
+    assert_eq!(el.attributes.0.len(), 1);
+}
+
+#[test]
+fn raw_text_opt_in() {
+    let el = parse_element(quote!(<pre raw class={"code"}>"<b>not a tag</b>"</pre>));
+
+    assert_eq!(el.attributes.0.len(), 2);
+    assert!(matches!(
+        &prop_tokens(&el, "children").0[0],
+        PartialExpr::RSX(node) if matches!(node.as_ref(), RSXNode::RawText(_))
+    ));
+
+    // `raw` is still a normal attribute when it has a value
+    let el = parse_element(quote!(<pre raw={true} />));
+    assert!(matches!(
+        &el.attributes.0[0],
+        RSXAttribute::Normal { name, .. } if name == "raw"
+    ));
+    // So are dashed names starting with `raw`
+    let el = parse_element(quote!(<div raw-data={x}>"text"</div>));
+    assert!(matches!(
+        &el.attributes.0[0],
+        RSXAttribute::Normal { name, .. } if name == "raw-data"
+    ));
+    assert!(matches!(
+        &prop_tokens(&el, "children").0[..],
+        [PartialExpr::RSX(node)] if matches!(node.as_ref(), RSXNode::Text(_))
+    ));
+}
+
+#[test]
+fn raw_marker_anywhere_in_the_attributes() {
+    for tokens in [
+        quote!(<pre class={c} raw>"<b>"</pre>),
+        quote!(<pre id={"a"} raw class={c}>"<b>"</pre>),
+    ] {
+        let el = parse_element(tokens);
+        assert!(matches!(
+            &prop_tokens(&el, "children").0[..],
+            [PartialExpr::RSX(node)] if matches!(node.as_ref(), RSXNode::RawText(_))
+        ));
+    }
+
+    let err = syn::parse2::<RSXNode>(quote!(<pre raw class={c} raw>"<b>"</pre>))
+        .expect_err("the marker is only allowed once");
+    assert_eq!(err.to_string(), "duplicate `raw` marker");
+}
+
+#[test]
+fn raw_text_rejects_rsx() {
+    let err = syn::parse2::<RSXNode>(quote!(<script><span /></script>))
+        .expect_err("raw text elements cannot contain RSX");
+    assert_eq!(
+        err.to_string(),
+        "`<script>` only accepts a single string literal; nested RSX is not allowed here"
+    );
+
+    let err = syn::parse2::<RSXNode>(quote!(<style>"a {}" "b {}"</style>))
+        .expect_err("raw text elements take a single literal");
+    assert!(err.to_string().starts_with("`<style>` only accepts"));
+}
+
+#[test]
+fn void_elements() {
+    // Void elements may be left unclosed
+    let el = parse_element(quote!(<p>"first line"<br>"second line"<img src={src} /></p>));
+
+    let children = &prop_tokens(&el, "children").0;
+    assert_eq!(children.len(), 4);
+    assert!(matches!(
+        &children[1],
+        PartialExpr::RSX(node) if matches!(node.as_ref(), RSXNode::RSXElement(br) if br.tag == "br")
+    ));
+
+    let el = parse_element(quote!(<input type={"text"}>));
+    assert_eq!(el.tag, "input");
+    assert_eq!(el.attributes.0.len(), 1);
+}
+
+#[test]
+fn void_elements_reject_children() {
+    let err = syn::parse2::<RSXNode>(quote!(<br>"text"</br>))
+        .expect_err("void elements cannot have children");
+    assert_eq!(
+        err.to_string(),
+        "`<br>` is a void element and cannot have children or a closing tag; write `<br />`"
+    );
+
+    syn::parse2::<RSXNode>(quote!(<div><img></img></div>))
+        .expect_err("void elements cannot have a closing tag");
+}
+
+#[test]
+fn many_void_siblings() {
+    // Each void element only looks ahead to the next closing tag, which used to be exponential
+    let source = format!("<form>{}</form>", "<br><input type={\"text\"}>".repeat(200));
+    let node = syn::parse_str::<RSXNode>(&source).unwrap();
+    let RSXNode::RSXElement(form) = node else {
+        panic!("expected an element");
+    };
+    assert_eq!(prop_tokens(&form, "children").0.len(), 400);
+
+    let source = format!("<div>{}</br></div>", "<br>".repeat(200));
+    syn::parse_str::<RSXNode>(&source).expect_err("the last void element has a closing tag");
+}
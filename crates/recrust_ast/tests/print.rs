@@ -0,0 +1,137 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+
+#[allow(dead_code)]
+mod utils;
+use recrust_ast::{PrintOptions, RSXNode, print_rsx, print_rsx_with_comments};
+use utils::parse_node;
+
+fn print(tokens: TokenStream) -> String {
+    let options = PrintOptions {
+        max_width: usize::MAX,
+        ..PrintOptions::default()
+    };
+    print_rsx(&parse_node(tokens), &options)
+}
+
+#[test]
+fn elements_and_attributes() {
+    assert_eq!(print(quote!(<div></div>)), "<div />");
+    assert_eq!(
+        print(
+            quote!(<input type={"text"} title?={title} class:active={is_active} style:width={px(20)} on:input={|e| set(e)} {..rest}>)
+        ),
+        "<input type={\"text\"} title?={title} class:active={is_active} style:width={px(20)} on:input={|e| set(e)} {..rest} />"
+    );
+    assert_eq!(
+        print(
+            quote!(<div class={["btn", if primary { "btn-primary" } else { "" }]} data-id={items[0].id} />)
+        ),
+        "<div class={[\"btn\", if primary { \"btn-primary\" } else { \"\" }]} data-id={items[0].id} />"
+    );
+}
+
+#[test]
+fn children() {
+    assert_eq!(
+        print(
+            quote!(<ul class={"list"}><li>"one"</li><li>{count + 1}</li><Item {..props} /><!-- "end" --></ul>)
+        ),
+        "<ul class={\"list\"}>\n    <li>\"one\"</li>\n    <li>{count + 1}</li>\n    <Item {..props} />\n    <!-- \"end\" -->\n</ul>"
+    );
+    assert_eq!(
+        print(quote!(<pre raw>r#"<b>"#</pre>)),
+        "<pre raw>r#\"<b>\"#</pre>"
+    );
+}
+
+#[test]
+fn nested_rsx_in_expressions() {
+    assert_eq!(
+        print(
+            quote!(<ul>{items.iter().map(|item| <li key={item.id}>{&item.name}</li>).collect::<Vec<_>>()}</ul>)
+        ),
+        "<ul>{items.iter().map(|item| <li key={item.id}>{&item.name}</li>).collect::<Vec<_>>()}</ul>"
+    );
+    assert_eq!(
+        print(quote!(<div slot={if !open { <span>"closed"</span> } else { <Open /> }} />)),
+        "<div slot={if !open { <span>\"closed\"</span> } else { <Open /> }} />"
+    );
+}
+
+#[test]
+fn configurable_indent() {
+    let node = parse_node(quote!(<div><p><b>"deep"</b><i /></p></div>));
+    let options = PrintOptions {
+        indent: "\t".to_string(),
+        ..PrintOptions::default()
+    };
+
+    assert_eq!(
+        print_rsx(&node, &options),
+        "<div>\n\t<p>\n\t\t<b>\"deep\"</b>\n\t\t<i />\n\t</p>\n</div>"
+    );
+}
+
+#[test]
+fn wraps_long_opening_tags() {
+    let node = parse_node(quote!(
+        <form><input type={"text"} name={"email"} placeholder={"you@example.com"} /><p id={"a"}>"text"</p></form>
+    ));
+    let options = PrintOptions {
+        max_width: 40,
+        ..PrintOptions::default()
+    };
+
+    assert_eq!(
+        print_rsx(&node, &options),
+        "<form>\n    <input\n        type={\"text\"}\n        name={\"email\"}\n        placeholder={\"you@example.com\"}\n    />\n    <p id={\"a\"}>\"text\"</p>\n</form>"
+    );
+}
+
+#[test]
+fn comments_in_front_of_children() {
+    let node = parse_node(quote!(<ul><li>"one"</li>"two"</ul>));
+    let mut calls = 0;
+    let printed = print_rsx_with_comments(&node, &PrintOptions::default(), &mut |_| {
+        calls += 1;
+        if calls == 3 {
+            vec!["// second".to_string()]
+        } else {
+            Vec::new()
+        }
+    });
+
+    assert_eq!(
+        printed,
+        "<ul>\n    <li>\"one\"</li>\n    // second\n    \"two\"\n</ul>"
+    );
+}
+
+#[test]
+fn round_trip() {
+    let node = parse_node(quote!(
+        <main>
+            <Header title={"Home"} on_close={move || close(&state)} />
+            <section>{ if a != b && x >= -1 { vec![<b />] } else { Vec::new() } }</section>
+        </main>
+    ));
+
+    let printed = print_rsx(&node, &PrintOptions::default());
+    let reparsed: RSXNode = syn::parse_str(&printed).expect("printed RSX should parse");
+
+    assert_eq!(
+        reparsed.to_token_stream().to_string(),
+        node.to_token_stream().to_string()
+    );
+}
+
+#[test]
+fn operators() {
+    assert_eq!(
+        print(
+            quote!(<p>{ if a != b && x >= -1 { move || close(&mut state) } else { |_| *count += 1 } }</p>)
+        ),
+        "<p>{if a != b && x >= -1 { move || close(&mut state) } else { |_| *count += 1 }}</p>"
+    );
+}
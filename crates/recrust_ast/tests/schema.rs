@@ -0,0 +1,111 @@
+use quote::quote;
+
+#[allow(dead_code)]
+mod utils;
+use recrust_ast::{
+    RSXAttribute, RSXNode,
+    schema::{check_element, check_schema},
+};
+use utils::parse_element;
+
+/// Parses and checks an element.
+fn check(tokens: proc_macro2::TokenStream) -> Result<(), String> {
+    syn::parse2::<RSXNode>(tokens)
+        .and_then(|node| match node {
+            RSXNode::RSXElement(el) => check_element(&el),
+            other => panic!("expected Element node, got {:?}", other),
+        })
+        .map_err(|err| err.to_string())
+}
+
+#[test]
+fn known_elements_and_attributes() {
+    assert_eq!(check(quote!(<a href={url} target={"_blank"} />)), Ok(()));
+    assert_eq!(
+        check(quote!(<img alt={""} src?={src} class:round={true} />)),
+        Ok(())
+    );
+    assert_eq!(
+        check(quote!(<meta http-equiv={"refresh"} content={"5"} />)),
+        Ok(())
+    );
+    assert_eq!(
+        check(quote!(<input type={"text"} on:input={set} {..rest} />)),
+        Ok(())
+    );
+}
+
+#[test]
+fn dashed_attribute_names() {
+    let el = parse_element(quote!(<div data-user-id={id} aria-label={"Close"} />));
+
+    assert!(matches!(
+        &el.attributes.0[0],
+        RSXAttribute::Normal { name, .. } if name == "data-user-id"
+    ));
+    assert_eq!(check_element(&el).map_err(|err| err.to_string()), Ok(()));
+}
+
+#[test]
+fn unknown_element() {
+    assert_eq!(
+        check(quote!(<dvi />)),
+        Err("unknown element `<dvi>`, did you mean `<div>`?".to_string())
+    );
+    assert_eq!(
+        check(quote!(<widget />)),
+        Err(
+            "unknown element `<widget>`; custom elements must contain a `-`, like `<my-widget>`"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn unknown_attribute() {
+    assert_eq!(
+        check(quote!(<a hreff={url} />)),
+        Err("unknown attribute `hreff` on `<a>`, did you mean `href`?".to_string())
+    );
+    assert_eq!(
+        check(quote!(<div href={url} />)),
+        Err(
+            "unknown attribute `href` on `<div>`; custom data must use a `data-` prefix"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn escape_hatches() {
+    // Custom elements are not checked
+    assert_eq!(check(quote!(<my_widget anything={1} />)), Ok(()));
+    assert_eq!(check(quote!(<my-widget anything={1} />)), Ok(()));
+    // Neither are the attributes of SVG elements
+    assert_eq!(check(quote!(<path d={"M0 0"} stroke-width={2} />)), Ok(()));
+    // HTML event attributes
+    assert_eq!(check(quote!(<button onclick={"go()"} />)), Ok(()));
+}
+
+#[test]
+fn checks_the_whole_tree() {
+    let node = syn::parse2::<RSXNode>(quote!(
+        <div>
+            <spna />
+            <my-widget><a hreff={url} /></my-widget>
+        </div>
+    ))
+    .unwrap();
+
+    let messages: Vec<_> = check_schema(&node)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "unknown element `<spna>`, did you mean `<span>`?",
+            "unknown attribute `hreff` on `<a>`, did you mean `href`?",
+        ]
+    );
+}
@@ -0,0 +1,76 @@
+use recrust_ast::{parse_template, serialize::Document};
+use serde_json::json;
+
+fn serialize(source: &str) -> serde_json::Value {
+    serde_json::to_value(Document::new(&parse_template(source).unwrap())).unwrap()
+}
+
+#[test]
+fn serializes_a_versioned_document() {
+    let value = serialize("<p class={\"intro\"}>\"Hello\"</p>");
+
+    assert_eq!(
+        value,
+        json!({
+            "version": 2,
+            "root": {
+                "type": "element",
+                "tag": "p",
+                "span": {
+                    "start": { "line": 1, "column": 1 },
+                    "end": { "line": 1, "column": 31 },
+                },
+                "tag_span": {
+                    "start": { "line": 1, "column": 2 },
+                    "end": { "line": 1, "column": 3 },
+                },
+                "open_tag": {
+                    "start": { "line": 1, "column": 1 },
+                    "end": { "line": 1, "column": 20 },
+                },
+                "close_tag": {
+                    "start": { "line": 1, "column": 27 },
+                    "end": { "line": 1, "column": 31 },
+                },
+                "attributes": [{
+                    "kind": "normal",
+                    "name": "class",
+                    "span": {
+                        "start": { "line": 1, "column": 4 },
+                        "end": { "line": 1, "column": 19 },
+                    },
+                    "value": { "source": "\"intro\"", "rsx": [] },
+                }],
+                "children": [{
+                    "type": "text",
+                    "value": "Hello",
+                    "span": {
+                        "start": { "line": 1, "column": 20 },
+                        "end": { "line": 1, "column": 27 },
+                    },
+                }],
+            },
+        })
+    );
+}
+
+#[test]
+fn serializes_components_directives_and_nested_rsx() {
+    let value = serialize(
+        "<List {..props} on:click={select}>\n    {items.iter().map(|item| <Item name={item} />)}\n</List>",
+    );
+    let root = &value["root"];
+
+    assert_eq!(root["type"], "component");
+    assert_eq!(root["attributes"][0]["kind"], "spread");
+    assert_eq!(root["attributes"][0]["source"], "props");
+    assert_eq!(root["attributes"][1]["kind"], "event");
+    assert_eq!(root["attributes"][1]["event"], "click");
+
+    let child = &root["children"][0];
+    assert_eq!(child["type"], "expression");
+    assert_eq!(child["span"]["start"], json!({ "line": 2, "column": 5 }));
+    assert_eq!(child["rsx"][0]["type"], "component");
+    assert_eq!(child["rsx"][0]["tag"], "Item");
+    assert_eq!(child["rsx"][0]["attributes"][0]["name"], "name");
+}
@@ -0,0 +1,99 @@
+use proc_macro2::Span;
+use recrust_ast::{RSXAttribute, RSXNode, SourceSpan};
+
+fn parse(source: &str) -> RSXNode {
+    syn::parse_str(source).expect("failed to parse Node")
+}
+
+/// 1-based line and column where the range starts and ends.
+fn range(span: SourceSpan) -> ((usize, usize), (usize, usize)) {
+    (position(span.start), end(span.end))
+}
+
+fn position(span: Span) -> (usize, usize) {
+    (span.start().line, span.start().column + 1)
+}
+
+fn end(span: Span) -> (usize, usize) {
+    (span.end().line, span.end().column + 1)
+}
+
+#[test]
+fn element_spans() {
+    let node = parse("<div id={\"main\"}>\n    \"Hello\"\n</div>");
+    let RSXNode::RSXElement(element) = &node else {
+        panic!("expected an element");
+    };
+
+    assert_eq!(range(node.span()), ((1, 1), (3, 7)));
+    assert_eq!(position(element.spans.lt), (1, 1));
+    assert_eq!(range(element.spans.open), ((1, 1), (1, 18)));
+    assert_eq!(range(element.spans.open_end), ((1, 17), (1, 18)));
+    assert_eq!(range(element.spans.close.unwrap()), ((3, 1), (3, 7)));
+    assert_eq!(
+        position(element.closing_tag.as_ref().unwrap().span()),
+        (3, 3)
+    );
+}
+
+#[test]
+fn self_closing_and_void_spans() {
+    let RSXNode::RSXElement(element) = parse("<input  />") else {
+        panic!("expected an element");
+    };
+    assert_eq!(range(element.spans.full), ((1, 1), (1, 11)));
+    assert_eq!(range(element.spans.open_end), ((1, 9), (1, 11)));
+    assert!(element.spans.close.is_none() && element.closing_tag.is_none());
+
+    let RSXNode::RSXElement(element) = parse("<br>") else {
+        panic!("expected an element");
+    };
+    assert_eq!(range(element.spans.full), ((1, 1), (1, 5)));
+
+    let RSXNode::RSXComponent(component) = parse("<Card />") else {
+        panic!("expected a component");
+    };
+    assert_eq!(range(component.spans.full), ((1, 1), (1, 9)));
+    assert!(component.closing_tag.is_none());
+}
+
+#[test]
+fn attribute_spans() {
+    let RSXNode::RSXElement(element) =
+        parse("<a href={url} on:click={go} {..rest}>\"Home\" {icon}</a>")
+    else {
+        panic!("expected an element");
+    };
+    let spans = element
+        .attributes
+        .0
+        .iter()
+        .map(RSXAttribute::spans)
+        .collect::<Vec<_>>();
+
+    assert_eq!(range(spans[0].full), ((1, 4), (1, 14)));
+    assert_eq!(position(spans[0].eq.unwrap()), (1, 8));
+    assert_eq!(range(spans[1].full), ((1, 15), (1, 28)));
+    assert_eq!(position(spans[1].eq.unwrap()), (1, 23));
+    assert_eq!(range(spans[2].full), ((1, 29), (1, 37)));
+    assert!(spans[2].eq.is_none());
+    // Children span from the first to the last child
+    assert_eq!(range(spans[3].full), ((1, 38), (1, 51)));
+}
+
+#[test]
+fn comment_and_expression_spans() {
+    assert_eq!(range(parse("<!-- \"note\" -->").span()), ((1, 1), (1, 16)));
+    assert_eq!(range(parse("{ count + 1 }").span()), ((1, 1), (1, 14)));
+    assert_eq!(range(parse("\"text\"").span()), ((1, 1), (1, 7)));
+}
+
+#[test]
+fn errors_cover_the_whole_range() {
+    let node = parse("<p>\n    \"a\"\n</p>");
+    let err = node.span().error("misplaced paragraph");
+
+    assert_eq!(err.to_string(), "misplaced paragraph");
+    assert_eq!(position(err.span()), (1, 1));
+    assert_eq!(end(err.span()), (3, 5));
+}
@@ -0,0 +1,112 @@
+use quote::{ToTokens, quote};
+use recrust_ast::{PartialExpr, RSXAttribute, RSXNode};
+
+fn parse(tokens: proc_macro2::TokenStream) -> syn::Result<RSXNode> {
+    syn::parse2(tokens)
+}
+
+/// The shared syntax, for both elements and components.
+fn both(element: &str, component: &str) -> [String; 2] {
+    [element.to_string(), component.to_string()]
+}
+
+#[test]
+fn elements_and_components_share_the_syntax() {
+    for tag in both("section", "Section") {
+        let source = format!("<{tag} raw id={{\"a\"}}>r\"<b>\"</{tag}>");
+        let node = parse(source.parse().unwrap()).unwrap();
+
+        let (attributes, closing_tag) = match &node {
+            RSXNode::RSXElement(element) => (&element.attributes, &element.closing_tag),
+            RSXNode::RSXComponent(component) => (&component.attributes, &component.closing_tag),
+            _ => panic!("expected a tag"),
+        };
+        assert_eq!(closing_tag.as_ref().unwrap().to_string(), tag);
+        let Some(RSXAttribute::Normal { value, .. }) = attributes.get("children") else {
+            panic!("expected children");
+        };
+        assert!(matches!(
+            &value.0[..],
+            [PartialExpr::RSX(child)] if matches!(child.as_ref(), RSXNode::RawText(_))
+        ));
+    }
+}
+
+#[test]
+fn elements_and_components_share_the_errors() {
+    for tag in both("div", "Panel") {
+        let source = format!("<{tag}>\"a\"</{tag}x>");
+        let err = parse(source.parse().unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("mismatched closing tag: expected `</{tag}>`")
+        );
+
+        let source = format!("<{tag} inner_html={{html}}>\"a\"</{tag}>");
+        let err = parse(source.parse().unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`inner_html` cannot be used on an element that also has children"
+        );
+    }
+}
+
+#[test]
+fn void_and_raw_text_tags_are_only_elements() {
+    assert!(parse(quote!(<br>)).is_ok());
+    assert!(parse(quote!(<Br>)).is_err());
+
+    let node = parse(quote!(<Style>"p { color: red; }"</Style>)).unwrap();
+    let RSXNode::RSXComponent(component) = node else {
+        panic!("expected a component");
+    };
+    let Some(RSXAttribute::Normal { value, .. }) = component.attributes.get("children") else {
+        panic!("expected children");
+    };
+    assert!(matches!(
+        &value.0[..],
+        [PartialExpr::RSX(child)] if matches!(child.as_ref(), RSXNode::Text(_))
+    ));
+}
+
+#[test]
+fn codegen_depends_on_the_kind() {
+    let element = parse(quote!(<div />)).unwrap();
+    let component = parse(quote!(<Card />)).unwrap();
+
+    assert_eq!(
+        element.to_token_stream().to_string(),
+        quote!(create_element("div", {
+            let mut __attrs = create_attributes();
+            __attrs
+        }))
+        .to_string()
+    );
+    assert_eq!(
+        component.to_token_stream().to_string(),
+        quote!(create_element(Card, {
+            let mut __attrs = create_attributes();
+            __attrs
+        }))
+        .to_string()
+    );
+}
+
+#[test]
+fn custom_element_names() {
+    let node = parse(quote!(<my-fancy-widget id={"a"}>"Hi"</my-fancy-widget>)).unwrap();
+    let RSXNode::RSXElement(element) = &node else {
+        panic!("expected an element");
+    };
+    assert_eq!(element.tag.to_string(), "my-fancy-widget");
+    assert!(
+        node.to_token_stream()
+            .to_string()
+            .starts_with("create_element (\"my-fancy-widget\"")
+    );
+
+    let err = parse(quote!(<my-widget></my-gadget>)).unwrap_err();
+    assert!(err.to_string().contains("my-widget"), "{}", err);
+    let err = parse(quote!(<My-Widget />)).unwrap_err();
+    assert_eq!(err.to_string(), "`My-Widget` is not a valid component name");
+}
@@ -0,0 +1,35 @@
+use recrust_ast::{RSXNode, TemplateError, parse_template};
+
+#[test]
+fn parses_a_template_file() {
+    let node = parse_template("<main>\n    <h1>\"Title\"</h1>\n    {content}\n</main>\n").unwrap();
+
+    assert!(matches!(node, RSXNode::RSXElement(element) if element.tag == "main"));
+}
+
+#[test]
+fn locates_errors_in_the_template() {
+    let err = parse_template("<main>\n    <h1>\"Title\"</h2>\n</main>\n").unwrap_err();
+
+    assert_eq!(
+        err,
+        TemplateError {
+            line: 2,
+            column: 18,
+            message: "mismatched closing tag: expected `</h1>`".to_string(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "2:18: mismatched closing tag: expected `</h1>`"
+    );
+}
+
+#[test]
+fn locates_lex_errors_and_unexpected_end() {
+    let err = parse_template("<div>\n  {\"unclosed\"\n</div>").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 3));
+
+    let err = parse_template("<ul>\n    <li>\"a\"</li>\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 17));
+}
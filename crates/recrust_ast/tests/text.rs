@@ -0,0 +1,19 @@
+use recrust_ast::{collapse_whitespace, decode_entities};
+
+#[test]
+fn entities() {
+    assert_eq!(
+        decode_entities("&copy; &#169; &#xA9; &unknown; & a"),
+        "© © © &unknown; & a"
+    );
+}
+
+#[test]
+fn whitespace() {
+    assert_eq!(
+        collapse_whitespace("\n    Hello,\n\n    world  !  "),
+        Some("Hello, world ! ".to_string())
+    );
+    assert_eq!(collapse_whitespace(" a "), Some(" a ".to_string()));
+    assert_eq!(collapse_whitespace("\n    "), None);
+}
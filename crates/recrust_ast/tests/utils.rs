@@ -25,7 +25,7 @@ pub fn prop_tokens<'a>(el: &'a RSXElement, name: &'a str) -> &'a recrust_ast::Ex
         .0
         .iter()
         .find_map(|attr| match attr {
-            RSXAttribute::Normal { name: n, value } if n.to_string() == name => Some(value),
+            RSXAttribute::Normal { name: n, value, .. } if n.to_string() == name => Some(value),
             _ => None,
         })
         .unwrap_or_else(|| panic!("missing prop `{name}`"))
@@ -0,0 +1,76 @@
+use proc_macro2::Span;
+use quote::{ToTokens, quote};
+use syn::LitStr;
+
+#[allow(dead_code)]
+mod utils;
+use recrust_ast::{
+    RSXElement,
+    visit::{self, Visit},
+    visit_mut::VisitMut,
+};
+use utils::parse_node;
+
+#[derive(Default)]
+struct Tags(Vec<String>);
+
+impl<'ast> Visit<'ast> for Tags {
+    fn visit_element(&mut self, element: &'ast RSXElement) {
+        self.0.push(element.tag.to_string());
+        visit::visit_element(self, element);
+    }
+}
+
+#[test]
+fn visits_nested_rsx() {
+    let node = parse_node(quote!(
+        <ul class:open={is_open} slot={<header />}>
+            {items.iter().map(|item| <li>{ if item.done { <s>{item}</s> } else { <b>{item}</b> } }</li>)}
+            <Footer>"Done"</Footer>
+        </ul>
+    ));
+
+    let mut tags = Tags::default();
+    tags.visit_node(&node);
+
+    assert_eq!(tags.0, ["ul", "header", "li", "s", "b"]);
+}
+
+#[test]
+fn visit_texts() {
+    #[derive(Default)]
+    struct Texts(Vec<String>);
+
+    impl<'ast> Visit<'ast> for Texts {
+        fn visit_text(&mut self, text: &'ast LitStr) {
+            self.0.push(text.value());
+        }
+    }
+
+    let node = parse_node(quote!(<p>"Hello, " <b>"world"</b> <Icon label={"!"} /></p>));
+
+    let mut texts = Texts::default();
+    texts.visit_node(&node);
+
+    // `label={"!"}` is a plain expression, not an RSX text node
+    assert_eq!(texts.0, ["Hello, ", "world"]);
+}
+
+#[test]
+fn visit_mut_rewrites_texts() {
+    struct Uppercase;
+
+    impl VisitMut for Uppercase {
+        fn visit_text_mut(&mut self, text: &mut LitStr) {
+            *text = LitStr::new(&text.value().to_uppercase(), Span::call_site());
+        }
+    }
+
+    let mut node = parse_node(quote!(<div title={"keep"}>"hello" {vec![<i>"nested"</i>]}</div>));
+    Uppercase.visit_node_mut(&mut node);
+
+    let tokens = node.to_token_stream().to_string();
+    assert!(tokens.contains("\"HELLO\""));
+    assert!(tokens.contains("\"NESTED\""));
+    assert!(tokens.contains("\"keep\""));
+}
@@ -1,13 +1,85 @@
+use std::path::Path;
+
 use proc_macro::TokenStream;
 use quote::quote;
-use recrust_ast::RSXNode;
+use recrust_ast::{Diagnostic, RSXNode, parse_template};
+use syn::LitStr;
 
 #[proc_macro]
 pub fn rsx(input: TokenStream) -> TokenStream {
     let node = syn::parse_macro_input!(input as RSXNode);
 
+    expand(&node).into()
+}
+
+/// Expands an external template, relative to the crate's `Cargo.toml`: `include_rsx!("page.rsx")`.
+///
+/// The file is also passed to `include_str!`, so that editing it triggers a recompilation.
+#[proc_macro]
+pub fn include_rsx(input: TokenStream) -> TokenStream {
+    let path = syn::parse_macro_input!(input as LitStr);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = Path::new(&manifest_dir).join(path.value());
+    let source = match std::fs::read_to_string(&full_path) {
+        Ok(source) => source,
+        Err(err) => {
+            let message = format!("couldn't read `{}`: {}", full_path.display(), err);
+            return syn::Error::new(path.span(), message)
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let node = match parse_template(&source) {
+        Ok(node) => node,
+        Err(err) => {
+            let message = format!("{}:{}", path.value(), err);
+            return syn::Error::new(path.span(), message)
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let full_path = full_path.to_string_lossy();
+    let expanded = expand(&node);
     quote! {
-        #node
+        {
+            const _: &str = include_str!(#full_path);
+            #expanded
+        }
     }
     .into()
 }
+
+/// Expands a parsed node, along with the diagnostics of the enabled checks.
+fn expand(node: &RSXNode) -> proc_macro2::TokenStream {
+    #[allow(unused_mut)]
+    let mut diagnostics = Vec::<Diagnostic>::new();
+    #[cfg(feature = "schema")]
+    diagnostics.extend(recrust_ast::schema::check_schema(node));
+    #[cfg(feature = "content-model")]
+    diagnostics.extend(recrust_ast::check_content_model(node));
+    #[cfg(feature = "a11y")]
+    diagnostics.extend(recrust_ast::check_accessibility(
+        node,
+        if cfg!(feature = "deny-a11y") {
+            recrust_ast::Level::Error
+        } else {
+            recrust_ast::Level::Warning
+        },
+    ));
+
+    if diagnostics.is_empty() {
+        return quote! {
+            #node
+        };
+    }
+
+    quote! {
+        {
+            #(#diagnostics)*
+            #node
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::{fs, path::Path};
+
+#[test]
+fn compile_fail() {
+    // `include_rsx!` reads templates relative to the crate being compiled, which for these cases
+    // is the project trybuild generates in the target directory
+    let project = Path::new(env!("CARGO_TARGET_TMPDIR")).join("../tests/trybuild/recrust_macro");
+    fs::create_dir_all(project.join("tests/ui")).unwrap();
+    for entry in fs::read_dir("tests/ui").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|extension| extension == "rsx") {
+            fs::copy(&path, project.join(&path)).unwrap();
+        }
+    }
+
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
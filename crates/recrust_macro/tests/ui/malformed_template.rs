@@ -0,0 +1,5 @@
+use recrust_macro::include_rsx;
+
+fn main() {
+    let _ = include_rsx!("tests/ui/malformed_template.rsx");
+}
@@ -0,0 +1,378 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use crate::{
+    Event, Listener, Listeners, VElement, VNode,
+    hooks::render_instance,
+    style::{merge_style, number_style},
+    vnode::{add_classes, remove_empty_class},
+};
+
+// ---------------------------------- Attributes ----------------------------------
+
+/// The value of an attribute pushed by `rsx!`: `id={"main"}`, `on:click={...}`, the children...
+#[derive(Clone)]
+pub enum AttributeValue {
+    Text(String),
+    Number(f64),
+    /// `true` sets the attribute without a value, `false` leaves it out
+    Bool(bool),
+    Node(VNode),
+    Listener(Listener),
+}
+
+impl From<&str> for AttributeValue {
+    fn from(text: &str) -> Self {
+        AttributeValue::Text(text.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(text: String) -> Self {
+        AttributeValue::Text(text)
+    }
+}
+
+impl From<&String> for AttributeValue {
+    fn from(text: &String) -> Self {
+        AttributeValue::Text(text.clone())
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Bool(value)
+    }
+}
+
+impl From<VNode> for AttributeValue {
+    fn from(node: VNode) -> Self {
+        AttributeValue::Node(node)
+    }
+}
+
+impl From<Listener> for AttributeValue {
+    fn from(listener: Listener) -> Self {
+        AttributeValue::Listener(listener)
+    }
+}
+
+macro_rules! number_values {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for AttributeValue {
+                fn from(value: $ty) -> Self {
+                    AttributeValue::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+number_values!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// The attributes of an element or component, in the order `rsx!` pushes them. Components
+/// receive them as their props.
+#[derive(Clone, Default)]
+pub struct Attributes(pub Vec<(String, AttributeValue)>);
+
+impl Attributes {
+    pub fn push<N: Into<String>, V: Into<AttributeValue>>(&mut self, (name, value): (N, V)) {
+        self.0.push((name.into(), value.into()));
+    }
+
+    /// The value of an attribute, the last one when it is set several times.
+    pub fn get(&self, name: &str) -> Option<&AttributeValue> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(other, _)| other == name)
+            .map(|(_, value)| value)
+    }
+
+    /// The value of an attribute as text, for text and number values.
+    pub fn text(&self, name: &str) -> Option<String> {
+        match self.get(name)? {
+            AttributeValue::Text(text) => Some(text.clone()),
+            AttributeValue::Number(number) => Some(number.to_string()),
+            _ => None,
+        }
+    }
+
+    /// The nested children, or an empty fragment.
+    pub fn children(&self) -> VNode {
+        match self.get("children") {
+            Some(AttributeValue::Node(node)) => node.clone(),
+            _ => VNode::fragment(Vec::new()),
+        }
+    }
+}
+
+/// Spreads: `{..attrs}`.
+impl<N: Into<String>, V: Into<AttributeValue>> Extend<(N, V)> for Attributes {
+    fn extend<I: IntoIterator<Item = (N, V)>>(&mut self, attributes: I) {
+        for attribute in attributes {
+            self.push(attribute);
+        }
+    }
+}
+
+// ---------------------------------- Tags ----------------------------------
+
+/// What `create_element` accepts as a tag: the name of an element, or a component, a function
+/// rendering its props: `fn card(props: Attributes) -> VNode`. Within
+/// [`Hooks::render`](crate::Hooks::render), each component instance renders with its own hooks.
+pub trait Tag {
+    fn create(self, attributes: Attributes) -> VNode;
+}
+
+impl Tag for &str {
+    fn create(self, attributes: Attributes) -> VNode {
+        let mut element = VElement {
+            tag: self.to_string(),
+            key: None,
+            attributes: BTreeMap::new(),
+            listeners: Listeners::default(),
+            children: Vec::new(),
+        };
+        let mut styles = Vec::new();
+
+        for (name, value) in attributes.0 {
+            match (name.as_str(), value) {
+                ("key", value) => element.key = text(value),
+                ("children", AttributeValue::Node(node)) => element.children = vec![node],
+                ("children", value) => {
+                    element.children = text(value).map(VNode::text).into_iter().collect()
+                }
+                ("inner_html", AttributeValue::Node(node)) => element.children = vec![node],
+                (directive, value) if directive.starts_with("style:") => {
+                    let property = &directive["style:".len()..];
+                    let value = match value {
+                        AttributeValue::Number(number) => Some(number_style(property, number)),
+                        value => text(value),
+                    };
+                    if let Some(value) = value {
+                        styles.push((property.to_string(), value));
+                    }
+                }
+                ("class", value) => {
+                    let value = text(value).unwrap_or_default();
+                    add_classes(element.attributes.entry(name).or_default(), &value);
+                }
+                (_, AttributeValue::Listener(listener)) => {
+                    let event = name.strip_prefix("on:").unwrap_or(&name).to_string();
+                    element.listeners.0.push((event, listener));
+                }
+                (_, AttributeValue::Bool(false)) => {
+                    element.attributes.remove(&name);
+                }
+                (_, AttributeValue::Node(_)) => {
+                    panic!(
+                        "the `{}` attribute of `<{}>` is a node, only `children` and `inner_html` can be",
+                        name, self
+                    )
+                }
+                (_, value) => {
+                    element
+                        .attributes
+                        .insert(name, text(value).unwrap_or_default());
+                }
+            }
+        }
+        remove_empty_class(&mut element.attributes);
+        merge_style(&mut element.attributes, styles);
+
+        VNode::Element(element)
+    }
+}
+
+impl<F: Fn(Attributes) -> VNode> Tag for F {
+    fn create(self, attributes: Attributes) -> VNode {
+        // `app::card` or `app::card::{{closure}}` is named `card`
+        let type_name = std::any::type_name::<F>();
+        let name = type_name
+            .split("::")
+            .filter(|segment| !segment.starts_with('{'))
+            .last()
+            .unwrap_or_default();
+        let key = attributes.text("key");
+        let rendered = render_instance(type_name, key.clone(), || self(attributes));
+        VNode::component(name, key, rendered)
+    }
+}
+
+/// The text of a text or number value, `""` for `true`.
+fn text(value: AttributeValue) -> Option<String> {
+    match value {
+        AttributeValue::Text(text) => Some(text),
+        AttributeValue::Number(number) => Some(number.to_string()),
+        AttributeValue::Bool(true) => Some(String::new()),
+        _ => None,
+    }
+}
+
+// ---------------------------------- Children ----------------------------------
+
+/// What can be nested between tags: nodes, strings and numbers as texts, and lists or options of
+/// those.
+pub trait IntoNode {
+    fn into_node(self) -> VNode;
+}
+
+impl IntoNode for VNode {
+    fn into_node(self) -> VNode {
+        self
+    }
+}
+
+impl IntoNode for &str {
+    fn into_node(self) -> VNode {
+        VNode::text(self)
+    }
+}
+
+impl IntoNode for String {
+    fn into_node(self) -> VNode {
+        VNode::text(self)
+    }
+}
+
+impl IntoNode for &String {
+    fn into_node(self) -> VNode {
+        VNode::text(self.clone())
+    }
+}
+
+impl IntoNode for char {
+    fn into_node(self) -> VNode {
+        VNode::text(self.to_string())
+    }
+}
+
+impl<T: IntoNode> IntoNode for Vec<T> {
+    fn into_node(self) -> VNode {
+        VNode::fragment(self.into_iter().map(IntoNode::into_node).collect())
+    }
+}
+
+impl<T: IntoNode> IntoNode for Option<T> {
+    fn into_node(self) -> VNode {
+        self.map_or_else(|| VNode::fragment(Vec::new()), IntoNode::into_node)
+    }
+}
+
+macro_rules! number_nodes {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoNode for $ty {
+                fn into_node(self) -> VNode {
+                    VNode::text(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+number_nodes!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+// ---------------------------------- Trusted HTML ----------------------------------
+
+/// Pre-rendered HTML, the only value `inner_html` accepts, so that inserting HTML without
+/// escaping it is always spelled out:
+///
+/// ```
+/// use recrust_macro::rsx;
+/// use recrust_runtime::*;
+///
+/// let node = rsx! { <article inner_html={Trusted::new("<b>bold</b>")} /> };
+/// assert_eq!(render_to_string(&node), "<article><b>bold</b></article>");
+/// ```
+///
+/// Strings are rejected at compile time:
+///
+/// ```compile_fail
+/// use recrust_macro::rsx;
+/// use recrust_runtime::*;
+///
+/// let html = String::from("<b>bold</b>");
+/// let node = rsx! { <article inner_html={html} /> };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trusted(String);
+
+impl Trusted {
+    pub fn new(html: impl Into<String>) -> Self {
+        Trusted(html.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+// ---------------------------------- Events ----------------------------------
+
+/// The payload a listener created by `create_listener::<E>` receives.
+pub trait EventPayload: 'static {
+    fn from_event(event: &mut Event) -> &mut Self;
+}
+
+impl EventPayload for Event {
+    fn from_event(event: &mut Event) -> &mut Self {
+        event
+    }
+}
+
+// The payload types of `recrust_ast::EVENTS`, which are all the same event in the in-memory DOM
+pub type AnimationEvent = Event;
+pub type ClipboardEvent = Event;
+pub type CompositionEvent = Event;
+pub type DragEvent = Event;
+pub type FocusEvent = Event;
+pub type InputEvent = Event;
+pub type KeyboardEvent = Event;
+pub type MouseEvent = Event;
+pub type PointerEvent = Event;
+pub type SubmitEvent = Event;
+pub type TouchEvent = Event;
+pub type TransitionEvent = Event;
+pub type WheelEvent = Event;
+
+// ---------------------------------- Expansion ----------------------------------
+
+/// The attribute list of an element or component, see [`Attributes`].
+pub fn create_attributes() -> Attributes {
+    Attributes::default()
+}
+
+/// An element, or the output of a component, see [`Tag`].
+pub fn create_element(tag: impl Tag, attributes: Attributes) -> VNode {
+    tag.create(attributes)
+}
+
+/// A child nested between tags, see [`IntoNode`].
+pub fn create_child(child: impl IntoNode) -> VNode {
+    child.into_node()
+}
+
+pub fn create_fragment(children: Vec<VNode>) -> VNode {
+    VNode::fragment(children)
+}
+
+pub fn create_comment(text: &str) -> VNode {
+    VNode::comment(text)
+}
+
+pub fn create_raw_text(text: &str) -> VNode {
+    VNode::raw_text(text)
+}
+
+/// The content of an element with an `inner_html` attribute: the HTML as raw text, which
+/// backends write without escaping.
+pub fn create_inner_html(html: Trusted) -> VNode {
+    VNode::raw_text(html.0)
+}
+
+/// The listener of an `on:event` attribute.
+pub fn create_listener<E: EventPayload>(handler: impl Fn(&mut E) + 'static) -> Listener {
+    Rc::new(move |event: &mut Event| handler(E::from_event(event)))
+}
@@ -0,0 +1,226 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    NodePath, Patch, Style, VNode,
+    vnode::{FlatNode, flatten},
+};
+
+// ---------------------------------- Reconciler ----------------------------------
+
+/// The patches turning the DOM rendered from `old` into the one rendered from `new`.
+///
+/// Both trees are flattened first, so the root nodes are the children of the empty path.
+/// Children with a key are matched by key and moved as few times as possible, other children
+/// are matched in order with the next one of the same kind. Nodes that cannot be matched, or
+/// whose tag or component changed, are removed and created again.
+pub fn diff(old: &VNode, new: &VNode) -> Vec<Patch> {
+    let mut patches = Vec::new();
+    diff_children(
+        &flatten(std::slice::from_ref(old)),
+        &flatten(std::slice::from_ref(new)),
+        &mut Vec::new(),
+        &mut patches,
+    );
+    patches
+}
+
+/// Updates a matched node in place.
+fn diff_node(old: &FlatNode, new: &FlatNode, path: &mut NodePath, patches: &mut Vec<Patch>) {
+    match (old.node, new.node) {
+        (VNode::Text { text: old }, VNode::Text { text: new })
+        | (VNode::Comment { text: old }, VNode::Comment { text: new })
+        | (VNode::RawText { text: old }, VNode::RawText { text: new }) => {
+            if old != new {
+                patches.push(Patch::SetText {
+                    path: path.clone(),
+                    text: new.clone(),
+                });
+            }
+        }
+        (VNode::Element(old), VNode::Element(new)) => {
+            for name in old.attributes.keys() {
+                if !new.attributes.contains_key(name) {
+                    patches.push(Patch::RemoveAttribute {
+                        path: path.clone(),
+                        name: name.clone(),
+                    });
+                }
+            }
+            for (name, value) in &new.attributes {
+                match (name.as_str(), old.attributes.get(name)) {
+                    (_, Some(current)) if current == value => {}
+                    ("style", Some(current)) => diff_style(current, value, path, patches),
+                    _ => patches.push(Patch::SetAttribute {
+                        path: path.clone(),
+                        name: name.clone(),
+                        value: value.clone(),
+                    }),
+                }
+            }
+
+            diff_children(
+                &flatten(&old.children),
+                &flatten(&new.children),
+                path,
+                patches,
+            );
+        }
+        _ => unreachable!("only nodes of the same kind are matched"),
+    }
+}
+
+/// Updates the properties of a `style` attribute one by one.
+fn diff_style(old: &str, new: &str, path: &NodePath, patches: &mut Vec<Patch>) {
+    let (old, new) = (Style::parse(old), Style::parse(new));
+    for (property, _) in &old.0 {
+        if new.get(property).is_none() {
+            patches.push(Patch::RemoveStyle {
+                path: path.clone(),
+                property: property.clone(),
+            });
+        }
+    }
+    for (property, value) in &new.0 {
+        if old.get(property) != Some(value.as_str()) {
+            patches.push(Patch::SetStyle {
+                path: path.clone(),
+                property: property.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+}
+
+/// Removes the old children without a match, moves and creates the others in the new order,
+/// then updates the matched ones.
+fn diff_children(
+    old: &[FlatNode],
+    new: &[FlatNode],
+    parent: &mut NodePath,
+    patches: &mut Vec<Patch>,
+) {
+    let matches = match_children(old, new);
+
+    // Remove from the end, so that the indices of the next removals stay valid
+    let matched: HashSet<usize> = matches.iter().flatten().copied().collect();
+    for index in (0..old.len()).rev() {
+        if !matched.contains(&index) {
+            patches.push(Patch::Remove {
+                parent: parent.clone(),
+                index,
+            });
+        }
+    }
+
+    // Place the children from the last one, in front of the previously placed one. The longest
+    // run of children already in order stays in place, and only the others are moved.
+    let stable = longest_increasing(&matches.iter().flatten().copied().collect::<Vec<_>>());
+    let mut current: Vec<Option<usize>> = (0..old.len())
+        .filter(|index| matched.contains(index))
+        .map(Some)
+        .collect();
+    let mut anchor = current.len();
+    for (index, matching) in matches.iter().enumerate().rev() {
+        let Some(old_index) = *matching else {
+            patches.push(Patch::Create {
+                parent: parent.clone(),
+                index: anchor,
+                node: new[index].to_dom(),
+            });
+            current.insert(anchor, None);
+            continue;
+        };
+
+        let position = current
+            .iter()
+            .position(|child| *child == Some(old_index))
+            .expect("matched children are kept");
+        if stable.contains(&old_index) || position + 1 == anchor {
+            anchor = position;
+            continue;
+        }
+
+        let to = if position < anchor {
+            anchor - 1
+        } else {
+            anchor
+        };
+        patches.push(Patch::Move {
+            parent: parent.clone(),
+            from: position,
+            to,
+        });
+        current.remove(position);
+        current.insert(to, Some(old_index));
+        anchor = to;
+    }
+
+    for (index, matching) in matches.iter().enumerate() {
+        if let Some(old_index) = *matching {
+            parent.push(index);
+            diff_node(&old[old_index], &new[index], parent, patches);
+            parent.pop();
+        }
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// The old child each new child is updated from, if any.
+///
+/// Keyed children match the old child with the same key, other children match the next old
+/// child without a key, in order. Either way, both have to be of the same kind.
+fn match_children(old: &[FlatNode], new: &[FlatNode]) -> Vec<Option<usize>> {
+    let mut keyed = HashMap::new();
+    for (index, child) in old.iter().enumerate() {
+        if let Some(key) = &child.key {
+            keyed.entry(key.as_str()).or_insert(index);
+        }
+    }
+
+    let mut used = vec![false; old.len()];
+    let mut next_unkeyed = 0;
+    new.iter()
+        .map(|child| {
+            let index = match &child.key {
+                Some(key) => keyed
+                    .get(key.as_str())
+                    .copied()
+                    .filter(|index| !used[*index]),
+                None => (next_unkeyed..old.len())
+                    .find(|index| old[*index].key.is_none() && old[*index].same_kind(child)),
+            }
+            .filter(|index| old[*index].same_kind(child))?;
+
+            if child.key.is_none() {
+                next_unkeyed = index + 1;
+            }
+            used[index] = true;
+            Some(index)
+        })
+        .collect()
+}
+
+/// The values of the longest strictly increasing subsequence.
+fn longest_increasing(values: &[usize]) -> HashSet<usize> {
+    // Index of the last value of the best subsequence of each length, and predecessors
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+    for (index, value) in values.iter().enumerate() {
+        let length = tails.partition_point(|tail| values[*tail] < *value);
+        previous[index] = length.checked_sub(1).map(|length| tails[length]);
+        if length == tails.len() {
+            tails.push(index);
+        } else {
+            tails[length] = index;
+        }
+    }
+
+    let mut subsequence = HashSet::new();
+    let mut index = tails.last().copied();
+    while let Some(current) = index {
+        subsequence.insert(values[current]);
+        index = previous[current];
+    }
+    subsequence
+}
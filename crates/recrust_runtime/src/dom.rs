@@ -0,0 +1,460 @@
+use std::{collections::BTreeMap, fmt::Write, rc::Rc};
+
+use crate::{
+    Patch, VNode, diff,
+    ssr::{VOID_TAGS, comment, escape},
+    style::edit_style,
+};
+
+// ---------------------------------- In-Memory DOM ----------------------------------
+
+/// A node of a [`Dom`], identified by its index in the DOM.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// A headless DOM for tests: renders virtual nodes, applies the patches of
+/// [`diff`](crate::diff), and dispatches events to listeners.
+///
+/// ```
+/// use recrust_macro::rsx;
+/// use recrust_runtime::*;
+///
+/// let dom = Dom::render(&rsx! { <button data-testid={"save"}>"Save"</button> });
+/// let button = dom.find_by_role("button").unwrap();
+/// assert_eq!(dom.find_by_text("Save"), Some(button));
+/// assert_eq!(dom.inner_html(dom.root()), "<button data-testid=\"save\">Save</button>");
+/// ```
+///
+/// Nodes are never freed: removed nodes are only detached from their parent.
+pub struct Dom {
+    nodes: Vec<DomNode>,
+}
+
+pub struct DomNode {
+    pub kind: DomNodeKind,
+    pub parent: Option<NodeId>,
+    pub children: Vec<NodeId>,
+    /// The listeners of the virtual element, replaced by [`Dom::update`]
+    handlers: Vec<(String, Listener)>,
+    listeners: Vec<(String, Listener)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DomNodeKind {
+    /// The container the rendered nodes are mounted in
+    Root,
+    Element {
+        tag: String,
+        attributes: BTreeMap<String, String>,
+    },
+    Text(String),
+    Comment(String),
+    /// The content of a raw text element, serialized without escaping
+    RawText(String),
+}
+
+/// An event listener, which may stop the propagation of the event.
+pub type Listener = Rc<dyn Fn(&mut Event)>;
+
+/// An event dispatched to a node, then bubbling up to its ancestors.
+#[derive(Clone, Debug)]
+pub struct Event {
+    pub name: String,
+    /// The node the event was dispatched to
+    pub target: NodeId,
+    /// The node whose listener is called
+    pub current_target: NodeId,
+    propagation_stopped: bool,
+}
+
+impl Event {
+    /// Stops the event from bubbling to the next ancestors, the other listeners of the current
+    /// node are still called.
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped
+    }
+}
+
+impl Dom {
+    /// An empty DOM, with only its root.
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![DomNode {
+                kind: DomNodeKind::Root,
+                parent: None,
+                children: Vec::new(),
+                handlers: Vec::new(),
+                listeners: Vec::new(),
+            }],
+        }
+    }
+
+    /// A DOM with the node rendered in its root, the listeners of its elements included:
+    /// `Dom::render(&rsx! { <button on:click={...}>"+1"</button> })`.
+    pub fn render(node: &VNode) -> Self {
+        let mut dom = Self::new();
+        for (index, child) in node.flatten().iter().enumerate() {
+            dom.mount(dom.root(), index, child);
+        }
+        dom
+    }
+
+    /// Applies the patches from `old` to `new`, then binds the listeners of `new`, which are
+    /// new closures on every render.
+    pub fn update(&mut self, old: &VNode, new: &VNode) {
+        self.apply(&diff(old, new));
+        self.bind(self.root(), &new.flatten());
+    }
+
+    /// Replaces the handlers of the children of `id` and their descendants with the listeners
+    /// of the flattened nodes they were rendered from.
+    fn bind(&mut self, id: NodeId, nodes: &[VNode]) {
+        let children = self.node(id).children.clone();
+        for (child, node) in children.into_iter().zip(nodes) {
+            if let VNode::Element(element) = node {
+                self.nodes[child.0].handlers = element.listeners.0.clone();
+                self.bind(child, &element.children);
+            }
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    pub fn node(&self, id: NodeId) -> &DomNode {
+        &self.nodes[id.0]
+    }
+
+    /// The node at a path of child indices from the root, like the paths of patches.
+    pub fn node_at(&self, path: &[usize]) -> Option<NodeId> {
+        path.iter().try_fold(self.root(), |id, index| {
+            self.node(id).children.get(*index).copied()
+        })
+    }
+
+    /// Creates the nodes rendered by `node` and inserts them from the `index`th child of `parent`.
+    pub fn mount(&mut self, parent: NodeId, index: usize, node: &VNode) {
+        for (offset, child) in node.flatten().iter().enumerate() {
+            let id = self.create(child);
+            self.nodes[id.0].parent = Some(parent);
+            self.nodes[parent.0].children.insert(index + offset, id);
+        }
+    }
+
+    /// Creates a flattened node and its descendants, without a parent.
+    fn create(&mut self, node: &VNode) -> NodeId {
+        let (kind, handlers, children) = match node {
+            VNode::Element(element) => (
+                DomNodeKind::Element {
+                    tag: element.tag.clone(),
+                    attributes: element.attributes.clone(),
+                },
+                element.listeners.0.clone(),
+                element.children.as_slice(),
+            ),
+            VNode::Text { text } => (DomNodeKind::Text(text.clone()), Vec::new(), &[][..]),
+            VNode::Comment { text } => (DomNodeKind::Comment(text.clone()), Vec::new(), &[][..]),
+            VNode::RawText { text } => (DomNodeKind::RawText(text.clone()), Vec::new(), &[][..]),
+            VNode::Fragment { .. } | VNode::Component(_) => {
+                unreachable!("nodes are flattened before being created")
+            }
+        };
+
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(DomNode {
+            kind,
+            parent: None,
+            children: Vec::new(),
+            handlers,
+            listeners: Vec::new(),
+        });
+        for child in children {
+            let child = self.create(child);
+            self.nodes[child.0].parent = Some(id);
+            self.nodes[id.0].children.push(child);
+        }
+        id
+    }
+
+    /// Applies patches in order, panicking on paths that don't exist.
+    pub fn apply(&mut self, patches: &[Patch]) {
+        for patch in patches {
+            match patch {
+                Patch::Create {
+                    parent,
+                    index,
+                    node,
+                } => {
+                    let parent = self.expect_id(parent);
+                    self.mount(parent, *index, node);
+                }
+                Patch::Remove { parent, index } => {
+                    let child = self.expect_node(parent).children.remove(*index);
+                    self.nodes[child.0].parent = None;
+                }
+                Patch::Move { parent, from, to } => {
+                    let children = &mut self.expect_node(parent).children;
+                    let child = children.remove(*from);
+                    children.insert(*to, child);
+                }
+                Patch::SetAttribute { path, name, value } => {
+                    if let DomNodeKind::Element { attributes, .. } =
+                        &mut self.expect_node(path).kind
+                    {
+                        attributes.insert(name.clone(), value.clone());
+                    }
+                }
+                Patch::RemoveAttribute { path, name } => {
+                    if let DomNodeKind::Element { attributes, .. } =
+                        &mut self.expect_node(path).kind
+                    {
+                        attributes.remove(name);
+                    }
+                }
+                Patch::SetStyle {
+                    path,
+                    property,
+                    value,
+                } => {
+                    if let DomNodeKind::Element { attributes, .. } =
+                        &mut self.expect_node(path).kind
+                    {
+                        edit_style(attributes, |style| style.set(property, value));
+                    }
+                }
+                Patch::RemoveStyle { path, property } => {
+                    if let DomNodeKind::Element { attributes, .. } =
+                        &mut self.expect_node(path).kind
+                    {
+                        edit_style(attributes, |style| style.remove(property));
+                    }
+                }
+                Patch::SetText { path, text } => match &mut self.expect_node(path).kind {
+                    DomNodeKind::Text(current)
+                    | DomNodeKind::Comment(current)
+                    | DomNodeKind::RawText(current) => *current = text.clone(),
+                    kind => panic!("cannot set the text of {:?}", kind),
+                },
+            }
+        }
+    }
+
+    fn expect_id(&self, path: &[usize]) -> NodeId {
+        self.node_at(path)
+            .unwrap_or_else(|| panic!("no node at {:?}", path))
+    }
+
+    fn expect_node(&mut self, path: &[usize]) -> &mut DomNode {
+        let id = self.expect_id(path);
+        &mut self.nodes[id.0]
+    }
+
+    /// The tag of an element.
+    pub fn tag(&self, id: NodeId) -> Option<&str> {
+        match &self.node(id).kind {
+            DomNodeKind::Element { tag, .. } => Some(tag),
+            _ => None,
+        }
+    }
+
+    pub fn attribute(&self, id: NodeId, name: &str) -> Option<&str> {
+        match &self.node(id).kind {
+            DomNodeKind::Element { attributes, .. } => attributes.get(name).map(String::as_str),
+            _ => None,
+        }
+    }
+
+    /// The texts of the node and its descendants, concatenated, without comments.
+    pub fn text_content(&self, id: NodeId) -> String {
+        match &self.node(id).kind {
+            DomNodeKind::Text(text) | DomNodeKind::RawText(text) => text.clone(),
+            DomNodeKind::Comment(_) => String::new(),
+            _ => self
+                .node(id)
+                .children
+                .iter()
+                .map(|child| self.text_content(*child))
+                .collect(),
+        }
+    }
+
+    /// The node and its descendants, in document order.
+    pub fn descendants(&self, id: NodeId) -> Vec<NodeId> {
+        let mut nodes = vec![id];
+        for child in &self.node(id).children {
+            nodes.extend(self.descendants(*child));
+        }
+        nodes
+    }
+
+    /// Serializes the content of a node to HTML, with void elements left unclosed.
+    pub fn inner_html(&self, id: NodeId) -> String {
+        let mut html = String::new();
+        for child in &self.node(id).children {
+            self.write_html(*child, &mut html);
+        }
+        html
+    }
+
+    fn write_html(&self, id: NodeId, html: &mut String) {
+        match &self.node(id).kind {
+            DomNodeKind::Root => {}
+            DomNodeKind::Text(text) => html.push_str(&escape(text)),
+            DomNodeKind::Comment(text) => html.push_str(&comment(text)),
+            DomNodeKind::RawText(text) => html.push_str(text),
+            DomNodeKind::Element { tag, attributes } => {
+                write!(html, "<{}", tag).unwrap();
+                for (name, value) in attributes {
+                    write!(html, " {}=\"{}\"", name, escape(value)).unwrap();
+                }
+                html.push('>');
+                if VOID_TAGS.contains(&tag.as_str()) {
+                    return;
+                }
+                html.push_str(&self.inner_html(id));
+                write!(html, "</{}>", tag).unwrap();
+            }
+        }
+    }
+
+    /// The first element whose own text, ignoring surrounding whitespace, is `text`.
+    pub fn find_by_text(&self, text: &str) -> Option<NodeId> {
+        self.find(|id| {
+            let own_text: String = self
+                .node(id)
+                .children
+                .iter()
+                .filter_map(|child| match &self.node(*child).kind {
+                    DomNodeKind::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            own_text.trim() == text
+        })
+    }
+
+    /// The first element with the role, given by its `role` attribute or implied by its tag:
+    /// `<button>` is a `button`, `<a href=...>` a `link`, `<h1>` a `heading`...
+    pub fn find_by_role(&self, role: &str) -> Option<NodeId> {
+        self.find(|id| self.role(id) == Some(role))
+    }
+
+    /// The first element with the `data-testid` attribute.
+    pub fn find_by_test_id(&self, test_id: &str) -> Option<NodeId> {
+        self.find(|id| self.attribute(id, "data-testid") == Some(test_id))
+    }
+
+    /// The first attached element matching the predicate, in document order.
+    fn find(&self, predicate: impl Fn(NodeId) -> bool) -> Option<NodeId> {
+        self.descendants(self.root())
+            .into_iter()
+            .find(|id| self.tag(*id).is_some() && predicate(*id))
+    }
+
+    /// The explicit or implicit ARIA role of an element.
+    pub fn role(&self, id: NodeId) -> Option<&str> {
+        if let Some(role) = self.attribute(id, "role") {
+            return role.split_whitespace().next();
+        }
+
+        let has = |name| self.attribute(id, name).is_some();
+        Some(match self.tag(id)? {
+            "a" | "area" if has("href") => "link",
+            "article" => "article",
+            "aside" => "complementary",
+            "button" => "button",
+            "dialog" => "dialog",
+            "footer" => "contentinfo",
+            "form" => "form",
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+            "header" => "banner",
+            "hr" => "separator",
+            "img" if self.attribute(id, "alt") != Some("") => "img",
+            "input" => match self.attribute(id, "type").unwrap_or("text") {
+                "button" | "image" | "reset" | "submit" => "button",
+                "checkbox" => "checkbox",
+                "radio" => "radio",
+                "range" => "slider",
+                "number" => "spinbutton",
+                "search" => "searchbox",
+                "email" | "tel" | "text" | "url" => "textbox",
+                _ => return None,
+            },
+            "li" => "listitem",
+            "main" => "main",
+            "nav" => "navigation",
+            "ol" | "ul" => "list",
+            "option" => "option",
+            "progress" => "progressbar",
+            "select" => "combobox",
+            "table" => "table",
+            "td" => "cell",
+            "textarea" => "textbox",
+            "th" => "columnheader",
+            "tr" => "row",
+            _ => return None,
+        })
+    }
+
+    /// Adds a listener for the event `name`: `"click"`, `"input"`... Unlike the listeners of
+    /// `on:event` attributes, it is kept by [`Dom::update`].
+    pub fn add_event_listener(
+        &mut self,
+        id: NodeId,
+        name: &str,
+        listener: impl Fn(&mut Event) + 'static,
+    ) {
+        self.nodes[id.0]
+            .listeners
+            .push((name.to_string(), Rc::new(listener)));
+    }
+
+    /// Dispatches an event to a node, then to each of its ancestors until a listener stops its
+    /// propagation. Returns the event as left by the listeners.
+    pub fn dispatch(&self, target: NodeId, name: &str) -> Event {
+        let mut event = Event {
+            name: name.to_string(),
+            target,
+            current_target: target,
+            propagation_stopped: false,
+        };
+
+        let mut current = Some(target);
+        while let Some(id) = current {
+            event.current_target = id;
+            // Listeners added while dispatching are only called by the next events
+            let node = self.node(id);
+            let listeners: Vec<Listener> = node
+                .handlers
+                .iter()
+                .chain(&node.listeners)
+                .filter(|(listener_name, _)| *listener_name == name)
+                .map(|(_, listener)| listener.clone())
+                .collect();
+            for listener in listeners {
+                listener(&mut event);
+            }
+
+            if event.propagation_stopped {
+                break;
+            }
+            current = self.node(id).parent;
+        }
+        event
+    }
+
+    pub fn click(&self, target: NodeId) -> Event {
+        self.dispatch(target, "click")
+    }
+}
+
+impl Default for Dom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,435 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    panic::Location,
+    rc::{Rc, Weak},
+};
+
+// ---------------------------------- Hooks ----------------------------------
+
+/// The hook slots of one component instance, kept between its renders.
+///
+/// Hooks are identified by the order they are called in, so a component has to call the same
+/// hooks in the same order on every render. With debug assertions, this is checked and a
+/// mismatch panics with the locations of both calls.
+///
+/// The components a render creates with `rsx!` are instances nested in the one rendering them,
+/// each with its own slots. They are identified by their `key`, or else by how many components
+/// of the same type were created before them by that render, and kept as long as every render
+/// creates them again:
+///
+/// ```
+/// use recrust_macro::rsx;
+/// use recrust_runtime::*;
+///
+/// fn counter(_: Attributes) -> VNode {
+///     let count = use_state(|| 0);
+///     count.update(|count| *count += 1);
+///     rsx! { <span>{count.get()}</span> }
+/// }
+///
+/// let app = Hooks::new();
+/// app.render(|| rsx! { <:counter /> });
+/// let node = app.render(|| rsx! { <:counter /> });
+/// assert_eq!(render_to_string(&node), "<span>2</span>");
+/// ```
+#[derive(Clone, Default)]
+pub struct Hooks(Rc<HooksInner>);
+
+#[derive(Default)]
+struct HooksInner {
+    slots: RefCell<Vec<Slot>>,
+    /// The index of the next hook called by the current render
+    cursor: Cell<usize>,
+    /// Whether a render completed, after which no new hook may be called
+    mounted: Cell<bool>,
+    /// Whether a state changed since the last render
+    dirty: Cell<bool>,
+    /// The slots of the effects to run after the render
+    pending_effects: RefCell<Vec<usize>>,
+    /// The nested component instances, in the order the last render created them
+    children: RefCell<Vec<(Instance, Hooks)>>,
+    /// The nested component instances created by the current render
+    rendered: RefCell<Vec<(Instance, Hooks)>>,
+    /// How many components of each type the current render created without a key
+    positions: RefCell<HashMap<&'static str, usize>>,
+}
+
+/// A component instance nested in another, by the type name of the component.
+#[derive(Clone, PartialEq, Eq)]
+enum Instance {
+    Keyed(&'static str, String),
+    Nth(&'static str, usize),
+}
+
+struct Slot {
+    value: Box<dyn Any>,
+    #[cfg(debug_assertions)]
+    hook: &'static str,
+    #[cfg(debug_assertions)]
+    location: &'static Location<'static>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Rc<HooksInner>>> = const { RefCell::new(None) };
+}
+
+impl Hooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders the component: calls `render`, during which the `use_*` functions use these
+    /// slots. Effects whose dependencies changed are scheduled, see [`Hooks::run_effects`].
+    pub fn render<R>(&self, render: impl FnOnce() -> R) -> R {
+        /// Restores the previous component, even if `render` panics.
+        struct Restore(Option<Rc<HooksInner>>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+
+        self.0.cursor.set(0);
+        self.0.dirty.set(false);
+        self.0.rendered.borrow_mut().clear();
+        self.0.positions.borrow_mut().clear();
+        let previous = CURRENT.with(|current| current.replace(Some(self.0.clone())));
+        let restore = Restore(previous);
+        let output = render();
+        drop(restore);
+
+        #[cfg(debug_assertions)]
+        {
+            let slots = self.0.slots.borrow();
+            let called = self.0.cursor.get();
+            if called < slots.len() {
+                let missing = &slots[called];
+                panic!(
+                    "hooks called in a different order: {} hooks were called, but the previous render also called `{}` at {}",
+                    called, missing.hook, missing.location
+                );
+            }
+        }
+
+        // The nested components that were not created again are removed
+        let rendered = std::mem::take(&mut *self.0.rendered.borrow_mut());
+        let removed = self.0.children.replace(rendered);
+        for (_, hooks) in removed {
+            hooks.unmount();
+        }
+
+        self.0.mounted.set(true);
+        output
+    }
+
+    /// Runs the effects scheduled by the last render, each after the cleanup of its previous
+    /// run, those of the nested components first. Renderers call it once the output of the
+    /// render is applied.
+    pub fn run_effects(&self) {
+        for hooks in self.children() {
+            hooks.run_effects();
+        }
+
+        let pending = std::mem::take(&mut *self.0.pending_effects.borrow_mut());
+        for index in pending {
+            // Take the effect out of its slot, it may use other hooks' handles while running
+            let (effect, cleanup) = {
+                let mut slots = self.0.slots.borrow_mut();
+                let slot = slots[index]
+                    .value
+                    .downcast_mut::<EffectSlot>()
+                    .expect("pending effects are effect slots");
+                (slot.effect.take(), slot.cleanup.take())
+            };
+
+            if let Some(cleanup) = cleanup {
+                cleanup();
+            }
+            if let Some(effect) = effect {
+                let cleanup = effect();
+                self.0.slots.borrow_mut()[index]
+                    .value
+                    .downcast_mut::<EffectSlot>()
+                    .expect("pending effects are effect slots")
+                    .cleanup = cleanup;
+            }
+        }
+    }
+
+    /// Runs the cleanups of every effect, those of the nested components first, when the
+    /// component is removed.
+    pub fn unmount(&self) {
+        for hooks in self.0.children.take() {
+            hooks.1.unmount();
+        }
+
+        self.0.pending_effects.borrow_mut().clear();
+        let cleanups: Vec<_> = self
+            .0
+            .slots
+            .borrow_mut()
+            .iter_mut()
+            .filter_map(|slot| slot.value.downcast_mut::<EffectSlot>())
+            .filter_map(|slot| slot.cleanup.take())
+            .collect();
+        for cleanup in cleanups {
+            cleanup();
+        }
+    }
+
+    /// Whether a state of the component or of a nested one was set since the last render,
+    /// which should render the component again.
+    pub fn is_dirty(&self) -> bool {
+        self.0.dirty.get() || self.children().iter().any(Hooks::is_dirty)
+    }
+
+    fn children(&self) -> Vec<Hooks> {
+        let children = self.0.children.borrow();
+        children.iter().map(|(_, hooks)| hooks.clone()).collect()
+    }
+}
+
+/// Renders a component created by the component being rendered, with the hooks of its instance.
+/// Outside of a render, `render` is called without hooks.
+pub(crate) fn render_instance<R>(
+    name: &'static str,
+    key: Option<String>,
+    render: impl FnOnce() -> R,
+) -> R {
+    let Some(parent) = CURRENT.with(|current| current.borrow().clone()) else {
+        return render();
+    };
+
+    let instance = match key {
+        Some(key) => Instance::Keyed(name, key),
+        None => {
+            let mut positions = parent.positions.borrow_mut();
+            let position = positions.entry(name).or_default();
+            *position += 1;
+            Instance::Nth(name, *position - 1)
+        }
+    };
+    if let Instance::Keyed(_, key) = &instance
+        && parent
+            .rendered
+            .borrow()
+            .iter()
+            .any(|(other, _)| *other == instance)
+    {
+        panic!("two `{}` components have the key `{}`", name, key);
+    }
+
+    let hooks = {
+        let mut children = parent.children.borrow_mut();
+        match children.iter().position(|(other, _)| *other == instance) {
+            Some(index) => children.remove(index).1,
+            None => Hooks::new(),
+        }
+    };
+    parent.rendered.borrow_mut().push((instance, hooks.clone()));
+    hooks.render(render)
+}
+
+/// The slot of the next hook of the component being rendered, created by `init` on the first
+/// render. Panics when called outside of [`Hooks::render`].
+#[track_caller]
+fn with_slot<S: 'static, R>(
+    hook: &'static str,
+    init: impl FnOnce() -> S,
+    f: impl FnOnce(&mut S, &Rc<HooksInner>, usize) -> R,
+) -> R {
+    let location = Location::caller();
+    let hooks = CURRENT
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(|| panic!("`{}` called outside of a component render", hook));
+
+    let index = hooks.cursor.get();
+    hooks.cursor.set(index + 1);
+
+    // Create the slot outside of the borrow, `init` may be arbitrary code
+    if index == hooks.slots.borrow().len() {
+        #[cfg(debug_assertions)]
+        if hooks.mounted.get() {
+            panic!(
+                "hooks called in a different order: `{}` at {} was not called by the previous render",
+                hook, location
+            );
+        }
+
+        let value = Box::new(init());
+        hooks.slots.borrow_mut().push(Slot {
+            value,
+            #[cfg(debug_assertions)]
+            hook,
+            #[cfg(debug_assertions)]
+            location,
+        });
+    }
+
+    let mut slots = hooks.slots.borrow_mut();
+    let slot = &mut slots[index];
+    #[cfg(debug_assertions)]
+    if slot.hook != hook || slot.location != location {
+        panic!(
+            "hooks called in a different order: hook {} is `{}` at {}, but was `{}` at {} in the previous render",
+            index, hook, location, slot.hook, slot.location
+        );
+    }
+    let _ = location;
+
+    let value = slot
+        .value
+        .downcast_mut::<S>()
+        .unwrap_or_else(|| panic!("hook {} changed type since the previous render", index));
+    f(value, &hooks, index)
+}
+
+// ---------------------------------- State ----------------------------------
+
+/// A handle on a state of a component; setting it marks the component as needing a render.
+pub struct State<T> {
+    value: Rc<RefCell<T>>,
+    hooks: Weak<HooksInner>,
+}
+
+impl<T> Clone for State<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            hooks: self.hooks.clone(),
+        }
+    }
+}
+
+impl<T: Clone> State<T> {
+    pub fn get(&self) -> T {
+        self.value.borrow().clone()
+    }
+}
+
+impl<T> State<T> {
+    pub fn set(&self, value: T) {
+        self.update(|current| *current = value);
+    }
+
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.value.borrow_mut());
+        if let Some(hooks) = self.hooks.upgrade() {
+            hooks.dirty.set(true);
+        }
+    }
+}
+
+/// A state kept between renders, initialized by `init` on the first one.
+#[track_caller]
+pub fn use_state<T: 'static>(init: impl FnOnce() -> T) -> State<T> {
+    with_slot(
+        "use_state",
+        || Rc::new(RefCell::new(init())),
+        |value: &mut Rc<RefCell<T>>, hooks, _| State {
+            value: value.clone(),
+            hooks: Rc::downgrade(hooks),
+        },
+    )
+}
+
+/// A value kept between renders, which doesn't render the component again when changed.
+#[track_caller]
+pub fn use_ref<T: 'static>(init: impl FnOnce() -> T) -> Rc<RefCell<T>> {
+    with_slot(
+        "use_ref",
+        || Rc::new(RefCell::new(init())),
+        |value: &mut Rc<RefCell<T>>, _, _| value.clone(),
+    )
+}
+
+// ---------------------------------- Memo / Effects ----------------------------------
+
+/// A value computed on the first render, and again only when the dependencies change.
+#[track_caller]
+pub fn use_memo<T: Clone + 'static, D: PartialEq + 'static>(
+    deps: D,
+    compute: impl FnOnce(&D) -> T,
+) -> T {
+    let memo = with_slot(
+        "use_memo",
+        || Rc::new(RefCell::new(None::<(D, T)>)),
+        |memo: &mut Rc<RefCell<Option<(D, T)>>>, _, _| memo.clone(),
+    );
+
+    if let Some((previous, value)) = &*memo.borrow()
+        && *previous == deps
+    {
+        return value.clone();
+    }
+
+    // Computed outside of any borrow, `compute` may be arbitrary code
+    let value = compute(&deps);
+    *memo.borrow_mut() = Some((deps, value.clone()));
+    value
+}
+
+/// What an effect returns: nothing, or a cleanup function run before the next run of the
+/// effect and when the component is unmounted.
+pub trait EffectCleanup {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>>;
+}
+
+impl EffectCleanup for () {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        None
+    }
+}
+
+impl<F: FnOnce() + 'static> EffectCleanup for F {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        Some(Box::new(self))
+    }
+}
+
+type Effect = Box<dyn FnOnce() -> Option<Box<dyn FnOnce()>>>;
+
+struct EffectSlot {
+    deps: Option<Box<dyn Any>>,
+    /// The effect to run after the render, if its dependencies changed
+    effect: Option<Effect>,
+    cleanup: Option<Box<dyn FnOnce()>>,
+}
+
+/// Schedules `effect` after the first render, and after every render that changes the
+/// dependencies: `use_effect(id, move || subscribe(id))`. `()` as dependencies runs it once.
+#[track_caller]
+pub fn use_effect<D: PartialEq + 'static, C: EffectCleanup>(
+    deps: D,
+    effect: impl FnOnce() -> C + 'static,
+) {
+    with_slot(
+        "use_effect",
+        || EffectSlot {
+            deps: None,
+            effect: None,
+            cleanup: None,
+        },
+        |slot: &mut EffectSlot, hooks, index| {
+            let changed = slot
+                .deps
+                .as_ref()
+                .and_then(|previous| previous.downcast_ref::<D>())
+                .is_none_or(|previous| *previous != deps);
+            if changed {
+                slot.deps = Some(Box::new(deps));
+                // An effect still pending from a previous render is replaced
+                if slot
+                    .effect
+                    .replace(Box::new(move || effect().into_cleanup()))
+                    .is_none()
+                {
+                    hooks.pending_effects.borrow_mut().push(index);
+                }
+            }
+        },
+    );
+}
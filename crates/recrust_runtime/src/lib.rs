@@ -0,0 +1,17 @@
+pub mod create;
+pub mod diff;
+pub mod dom;
+pub mod hooks;
+pub mod patch;
+pub mod ssr;
+pub mod style;
+pub mod vnode;
+
+pub use create::*;
+pub use diff::*;
+pub use dom::*;
+pub use hooks::*;
+pub use patch::*;
+pub use ssr::*;
+pub use style::*;
+pub use vnode::*;
@@ -0,0 +1,64 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::VNode;
+
+// ---------------------------------- Patches ----------------------------------
+
+/// The path of a node from the root: the index of each ancestor among its siblings, then the
+/// index of the node. Indices count elements, texts and comments only, fragments and
+/// components are flattened like in a DOM.
+pub type NodePath = Vec<usize>;
+
+/// One operation turning the old tree into the new one.
+///
+/// Patches are applied in order and each path or index refers to the tree as left by the
+/// previous patches. With the `serde` feature, they serialize to JSON tagged by `op`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "op", rename_all = "snake_case"))]
+pub enum Patch {
+    /// Inserts a new node, an element, a text or a comment, as the `index`th child of `parent`.
+    Create {
+        parent: NodePath,
+        index: usize,
+        node: VNode,
+    },
+    /// Removes the `index`th child of `parent`, along with its descendants.
+    Remove {
+        parent: NodePath,
+        index: usize,
+    },
+    /// Takes out the `from`th child of `parent` and inserts it back as its `to`th child.
+    Move {
+        parent: NodePath,
+        from: usize,
+        to: usize,
+    },
+    SetAttribute {
+        path: NodePath,
+        name: String,
+        value: String,
+    },
+    RemoveAttribute {
+        path: NodePath,
+        name: String,
+    },
+    /// Sets one property of the `style` attribute of an element that already has one, keeping
+    /// the others.
+    SetStyle {
+        path: NodePath,
+        property: String,
+        value: String,
+    },
+    /// Removes one property of the `style` attribute, and the attribute with the last one.
+    RemoveStyle {
+        path: NodePath,
+        property: String,
+    },
+    /// Replaces the text of a text, a comment or raw text.
+    SetText {
+        path: NodePath,
+        text: String,
+    },
+}
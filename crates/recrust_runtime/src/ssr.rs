@@ -0,0 +1,65 @@
+use std::fmt::Write;
+
+use crate::VNode;
+
+// ---------------------------------- Server-Side Rendering ----------------------------------
+
+/// Renders a node to HTML: texts and attributes are escaped, void elements have no closing tag,
+/// comments are kept as HTML comments and raw text is written as it is.
+pub fn render_to_string(node: &VNode) -> String {
+    let mut html = String::new();
+    write_node(node, &mut html);
+    html
+}
+
+fn write_node(node: &VNode, html: &mut String) {
+    match node {
+        VNode::Element(element) => {
+            write!(html, "<{}", element.tag).unwrap();
+            for (name, value) in &element.attributes {
+                write!(html, " {}=\"{}\"", name, escape(value)).unwrap();
+            }
+            html.push('>');
+            if VOID_TAGS.contains(&element.tag.as_str()) {
+                return;
+            }
+            for child in &element.children {
+                write_node(child, html);
+            }
+            write!(html, "</{}>", element.tag).unwrap();
+        }
+        VNode::Text { text } => html.push_str(&escape(text)),
+        VNode::Comment { text } => html.push_str(&comment(text)),
+        VNode::RawText { text } => html.push_str(text),
+        VNode::Fragment { children } => {
+            for child in children {
+                write_node(child, html);
+            }
+        }
+        VNode::Component(component) => write_node(&component.rendered, html),
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// Tags serialized without a closing tag.
+pub(crate) const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// An HTML comment, which can't be closed early by its text: a space follows every `-` and
+/// precedes a leading `>`, so the text never contains `--`, starts with `>` or `->`, or ends
+/// with `-`.
+pub(crate) fn comment(text: &str) -> String {
+    let text = text.replace('-', "- ");
+    let space = if text.starts_with('>') { " " } else { "" };
+    format!("<!--{}{}-->", space, text)
+}
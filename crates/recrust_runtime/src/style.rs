@@ -0,0 +1,157 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+// ---------------------------------- Styles ----------------------------------
+
+/// The declarations of a `style` attribute, in order: `color: red; width: 20px`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Style(pub Vec<(String, String)>);
+
+impl Style {
+    /// Parses the text of a `style` attribute, leaving out declarations without a property or
+    /// a value. Semicolons inside of quotes or parentheses, like in `url("a;b")`, are kept.
+    pub fn parse(text: &str) -> Self {
+        let mut style = Style::default();
+        for declaration in split_declarations(text) {
+            if let Some((property, value)) = declaration.split_once(':') {
+                let (property, value) = (property.trim(), value.trim());
+                if !property.is_empty() && !value.is_empty() {
+                    style.set(property, value);
+                }
+            }
+        }
+        style
+    }
+
+    pub fn get(&self, property: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(other, _)| other == property)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets a property, in place when it is already declared.
+    pub fn set(&mut self, property: impl Into<String>, value: impl Into<String>) {
+        let (property, value) = (property.into(), value.into());
+        match self.0.iter_mut().find(|(other, _)| *other == property) {
+            Some((_, current)) => *current = value,
+            None => self.0.push((property, value)),
+        }
+    }
+
+    pub fn remove(&mut self, property: &str) {
+        self.0.retain(|(other, _)| other != property);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Serializes the declarations as the text of a `style` attribute, which backends escape like
+/// any other attribute value.
+impl Display for Style {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (property, value)) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}: {}", property, value)?;
+        }
+        Ok(())
+    }
+}
+
+fn split_declarations(text: &str) -> Vec<&str> {
+    let mut declarations = Vec::new();
+    let (mut start, mut depth, mut quote) = (0, 0usize, None);
+    for (index, char) in text.char_indices() {
+        match (quote, char) {
+            (Some(open), _) if char == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(char),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                declarations.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations.push(&text[start..]);
+    declarations
+}
+
+// ---------------------------------- Directives ----------------------------------
+
+/// Properties whose numbers are not lengths, so `style:opacity={0.5}` doesn't get a unit.
+const UNITLESS_PROPERTIES: &[&str] = &[
+    "animation-iteration-count",
+    "aspect-ratio",
+    "border-image-outset",
+    "border-image-slice",
+    "border-image-width",
+    "column-count",
+    "columns",
+    "fill-opacity",
+    "flex",
+    "flex-grow",
+    "flex-shrink",
+    "flood-opacity",
+    "font-weight",
+    "grid-area",
+    "grid-column",
+    "grid-column-end",
+    "grid-column-start",
+    "grid-row",
+    "grid-row-end",
+    "grid-row-start",
+    "line-clamp",
+    "line-height",
+    "opacity",
+    "order",
+    "orphans",
+    "stop-opacity",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "tab-size",
+    "widows",
+    "z-index",
+    "zoom",
+];
+
+/// The value of a number for a property: in pixels, unless it is unitless or zero.
+pub fn number_style(property: &str, number: f64) -> String {
+    if number == 0.0 || property.starts_with("--") || UNITLESS_PROPERTIES.contains(&property) {
+        number.to_string()
+    } else {
+        format!("{}px", number)
+    }
+}
+
+/// Edits the declarations of the `style` attribute, which is left out when none remains.
+pub(crate) fn edit_style(attributes: &mut BTreeMap<String, String>, edit: impl FnOnce(&mut Style)) {
+    let mut style = Style::parse(attributes.get("style").map_or("", String::as_str));
+    edit(&mut style);
+    if style.is_empty() {
+        attributes.remove("style");
+    } else {
+        attributes.insert("style".to_string(), style.to_string());
+    }
+}
+
+/// Merges `style:property` directives into the literal `style` attribute, which they take
+/// precedence over. Directives with an empty value are left out.
+pub(crate) fn merge_style(
+    attributes: &mut BTreeMap<String, String>,
+    directives: Vec<(String, String)>,
+) {
+    edit_style(attributes, |style| {
+        for (property, value) in directives {
+            if !value.trim().is_empty() {
+                style.set(property, value);
+            }
+        }
+    });
+}
@@ -0,0 +1,253 @@
+use std::{collections::BTreeMap, fmt::Debug};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Listener, style::merge_style};
+
+// ---------------------------------- Virtual Nodes ----------------------------------
+
+/// A node of the tree a render produces, which [`diff`](crate::diff) compares to the previous one.
+///
+/// With the `serde` feature, nodes serialize to JSON tagged by `type`, without the listeners of
+/// their elements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum VNode {
+    Element(VElement),
+    Text {
+        text: String,
+    },
+    /// An HTML comment: `<!-- text -->`
+    Comment {
+        text: String,
+    },
+    /// The content of a raw text element like `<script>`, which is not escaped
+    RawText {
+        text: String,
+    },
+    /// Several nodes without a wrapper, their children are inserted in the parent directly
+    Fragment {
+        children: Vec<VNode>,
+    },
+    Component(VComponent),
+}
+
+/// An intrinsic element: `<li key={id} class={"item"}>...</li>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VElement {
+    pub tag: String,
+    /// Identifies the element among its siblings, so that it is moved rather than recreated
+    pub key: Option<String>,
+    pub attributes: BTreeMap<String, String>,
+    /// The listeners of `on:event` attributes, which are not serialized
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub listeners: Listeners,
+    pub children: Vec<VNode>,
+}
+
+/// The event listeners of an element, by event name: `("click", listener)`.
+///
+/// Listeners are closures, so two lists are equal when they listen to the same events: a
+/// backend binds the new listeners when it updates an element.
+#[derive(Clone, Default)]
+pub struct Listeners(pub Vec<(String, Listener)>);
+
+impl Listeners {
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+impl Debug for Listeners {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl PartialEq for Listeners {
+    fn eq(&self, other: &Self) -> bool {
+        self.names().eq(other.names())
+    }
+}
+
+impl Eq for Listeners {}
+
+/// The output of a component, which is replaced rather than diffed when the component changes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VComponent {
+    pub name: String,
+    pub key: Option<String>,
+    pub rendered: Box<VNode>,
+}
+
+impl VNode {
+    /// An element, the `key` attribute is taken out of the attributes as its key, repeated
+    /// `class` attributes are merged and so are `style:property` ones with `style`.
+    pub fn element<N: Into<String>, V: Into<String>>(
+        tag: impl Into<String>,
+        attributes: impl IntoIterator<Item = (N, V)>,
+        children: Vec<VNode>,
+    ) -> Self {
+        let (mut merged, mut styles) = (BTreeMap::new(), Vec::new());
+        for (name, value) in attributes {
+            let (name, value): (String, String) = (name.into(), value.into());
+            if name == "class" {
+                add_classes(merged.entry(name).or_default(), &value);
+            } else if let Some(property) = name.strip_prefix("style:") {
+                styles.push((property.to_string(), value));
+            } else {
+                merged.insert(name, value);
+            }
+        }
+        remove_empty_class(&mut merged);
+        merge_style(&mut merged, styles);
+        let key = merged.remove("key");
+
+        VNode::Element(VElement {
+            tag: tag.into(),
+            key,
+            attributes: merged,
+            listeners: Listeners::default(),
+            children,
+        })
+    }
+
+    pub fn text(text: impl Into<String>) -> Self {
+        VNode::Text { text: text.into() }
+    }
+
+    pub fn comment(text: impl Into<String>) -> Self {
+        VNode::Comment { text: text.into() }
+    }
+
+    pub fn raw_text(text: impl Into<String>) -> Self {
+        VNode::RawText { text: text.into() }
+    }
+
+    pub fn fragment(children: Vec<VNode>) -> Self {
+        VNode::Fragment { children }
+    }
+
+    pub fn component(name: impl Into<String>, key: Option<String>, rendered: VNode) -> Self {
+        VNode::Component(VComponent {
+            name: name.into(),
+            key,
+            rendered: Box::new(rendered),
+        })
+    }
+
+    /// The elements, texts and comments this node renders to, with fragments and components
+    /// flattened: the nodes a DOM actually holds.
+    pub fn flatten(&self) -> Vec<VNode> {
+        flatten(std::slice::from_ref(self))
+            .into_iter()
+            .map(|node| node.to_dom())
+            .collect()
+    }
+}
+
+// ---------------------------------- Other ----------------------------------
+
+/// An element, text or comment once fragments and components are flattened, along with what identifies
+/// it among its siblings.
+#[derive(Clone, Debug)]
+pub(crate) struct FlatNode<'a> {
+    pub node: &'a VNode,
+    /// The components rendering it, outermost first
+    pub components: Vec<&'a str>,
+    pub key: Option<String>,
+}
+
+impl FlatNode<'_> {
+    /// Whether two nodes can be updated in place rather than replaced.
+    pub fn same_kind(&self, other: &FlatNode) -> bool {
+        self.components == other.components
+            && match (self.node, other.node) {
+                (VNode::Element(a), VNode::Element(b)) => a.tag == b.tag,
+                (VNode::Text { .. }, VNode::Text { .. })
+                | (VNode::Comment { .. }, VNode::Comment { .. })
+                | (VNode::RawText { .. }, VNode::RawText { .. }) => true,
+                _ => false,
+            }
+    }
+
+    /// The node with its children flattened.
+    pub fn to_dom(&self) -> VNode {
+        match self.node {
+            VNode::Element(element) => VNode::Element(VElement {
+                children: flatten(&element.children)
+                    .into_iter()
+                    .map(|child| child.to_dom())
+                    .collect(),
+                ..element.clone()
+            }),
+            node => node.clone(),
+        }
+    }
+}
+
+/// Flattens fragments and components into the elements, texts and comments they render.
+///
+/// The key of a component is prefixed to the keys of the nodes it renders (or their position),
+/// so that they move along with it.
+pub(crate) fn flatten(nodes: &[VNode]) -> Vec<FlatNode<'_>> {
+    let mut flat = Vec::new();
+    for node in nodes {
+        match node {
+            VNode::Element(element) => flat.push(FlatNode {
+                node,
+                components: Vec::new(),
+                key: element.key.clone(),
+            }),
+            VNode::Text { .. } | VNode::Comment { .. } | VNode::RawText { .. } => {
+                flat.push(FlatNode {
+                    node,
+                    components: Vec::new(),
+                    key: None,
+                })
+            }
+            VNode::Fragment { children } => flat.extend(flatten(children)),
+            VNode::Component(component) => {
+                for (index, mut child) in flatten(std::slice::from_ref(&component.rendered))
+                    .into_iter()
+                    .enumerate()
+                {
+                    child.components.insert(0, &component.name);
+                    if let Some(key) = &component.key {
+                        child.key = Some(match child.key {
+                            Some(inner) => format!("{}/{}", key, inner),
+                            None => format!("{}/#{}", key, index),
+                        });
+                    }
+                    flat.push(child);
+                }
+            }
+        }
+    }
+    flat
+}
+
+// ---------------------------------- Classes ----------------------------------
+
+/// Adds the classes of `value` to a `class` attribute, leaving out empty and repeated ones.
+pub(crate) fn add_classes(class: &mut String, value: &str) {
+    for name in value.split_whitespace() {
+        if !class.split(' ').any(|existing| existing == name) {
+            if !class.is_empty() {
+                class.push(' ');
+            }
+            class.push_str(name);
+        }
+    }
+}
+
+/// Leaves out a `class` attribute without classes, like `class={[""]}`.
+pub(crate) fn remove_empty_class(attributes: &mut BTreeMap<String, String>) {
+    if attributes.get("class").is_some_and(String::is_empty) {
+        attributes.remove("class");
+    }
+}
@@ -0,0 +1,352 @@
+use recrust_runtime::{Patch, Style, VNode, diff};
+use serde_json::json;
+
+fn list(keys: &[&str]) -> VNode {
+    VNode::element(
+        "ul",
+        [("class", "list")],
+        keys.iter()
+            .map(|key| VNode::element("li", [("key", *key)], vec![VNode::text(*key)]))
+            .collect(),
+    )
+}
+
+/// Applies patches to the flattened children of a root, like a DOM backend would.
+fn apply(root: &mut Vec<VNode>, patches: &[Patch]) {
+    fn children<'a>(root: &'a mut Vec<VNode>, path: &[usize]) -> &'a mut Vec<VNode> {
+        path.iter()
+            .fold(root, |children, index| match &mut children[*index] {
+                VNode::Element(element) => &mut element.children,
+                node => panic!("{:?} has no children", node),
+            })
+    }
+
+    for patch in patches {
+        match patch {
+            Patch::Create {
+                parent,
+                index,
+                node,
+            } => children(root, parent).insert(*index, node.clone()),
+            Patch::Remove { parent, index } => {
+                children(root, parent).remove(*index);
+            }
+            Patch::Move { parent, from, to } => {
+                let children = children(root, parent);
+                let node = children.remove(*from);
+                children.insert(*to, node);
+            }
+            Patch::SetAttribute { path, name, value } => {
+                let (index, parent) = path.split_last().unwrap();
+                let VNode::Element(element) = &mut children(root, parent)[*index] else {
+                    panic!("not an element");
+                };
+                element.attributes.insert(name.clone(), value.clone());
+            }
+            Patch::RemoveAttribute { path, name } => {
+                let (index, parent) = path.split_last().unwrap();
+                let VNode::Element(element) = &mut children(root, parent)[*index] else {
+                    panic!("not an element");
+                };
+                element.attributes.remove(name);
+            }
+            Patch::SetStyle {
+                path,
+                property,
+                value,
+            } => {
+                let (index, parent) = path.split_last().unwrap();
+                let VNode::Element(element) = &mut children(root, parent)[*index] else {
+                    panic!("not an element");
+                };
+                let mut style = Style::parse(&element.attributes["style"]);
+                style.set(property, value);
+                element.attributes.insert("style".into(), style.to_string());
+            }
+            Patch::RemoveStyle { path, property } => {
+                let (index, parent) = path.split_last().unwrap();
+                let VNode::Element(element) = &mut children(root, parent)[*index] else {
+                    panic!("not an element");
+                };
+                let mut style = Style::parse(&element.attributes["style"]);
+                style.remove(property);
+                if style.is_empty() {
+                    element.attributes.remove("style");
+                } else {
+                    element.attributes.insert("style".into(), style.to_string());
+                }
+            }
+            Patch::SetText { path, text } => {
+                let (index, parent) = path.split_last().unwrap();
+                children(root, parent)[*index] = VNode::text(text.clone());
+            }
+        }
+    }
+}
+
+/// Diffs two trees and checks that the patches turn the first into the second.
+fn check(old: &VNode, new: &VNode) -> Vec<Patch> {
+    let patches = diff(old, new);
+    let mut root = old.flatten();
+    apply(&mut root, &patches);
+    assert_eq!(root, new.flatten(), "patches: {:#?}", patches);
+    patches
+}
+
+fn moves(patches: &[Patch]) -> usize {
+    patches
+        .iter()
+        .filter(|patch| matches!(patch, Patch::Move { .. }))
+        .count()
+}
+
+#[test]
+fn updates_text_and_attributes_in_place() {
+    let old = VNode::element(
+        "p",
+        [("id", "intro"), ("title", "Old")],
+        vec![VNode::text("Hello")],
+    );
+    let new = VNode::element(
+        "p",
+        [("class", "lead"), ("title", "New")],
+        vec![VNode::text("Hi")],
+    );
+
+    assert_eq!(
+        check(&old, &new),
+        vec![
+            Patch::RemoveAttribute {
+                path: vec![0],
+                name: "id".into(),
+            },
+            Patch::SetAttribute {
+                path: vec![0],
+                name: "class".into(),
+                value: "lead".into(),
+            },
+            Patch::SetAttribute {
+                path: vec![0],
+                name: "title".into(),
+                value: "New".into(),
+            },
+            Patch::SetText {
+                path: vec![0, 0],
+                text: "Hi".into(),
+            },
+        ]
+    );
+    assert!(check(&old, &old).is_empty());
+}
+
+#[test]
+fn updates_styles_property_by_property() {
+    let old = VNode::element(
+        "p",
+        [("style", "color: red; margin: 0; width: 20px")],
+        Vec::new(),
+    );
+    let new = VNode::element(
+        "p",
+        [("style", "color: blue; margin: 0"), ("style:width", "10px")],
+        Vec::new(),
+    );
+
+    assert_eq!(
+        check(&old, &new),
+        vec![
+            Patch::SetStyle {
+                path: vec![0],
+                property: "color".into(),
+                value: "blue".into(),
+            },
+            Patch::SetStyle {
+                path: vec![0],
+                property: "width".into(),
+                value: "10px".into(),
+            },
+        ]
+    );
+
+    let new = VNode::element("p", [("style", "margin: 0")], Vec::new());
+    assert_eq!(
+        check(&old, &new),
+        vec![
+            Patch::RemoveStyle {
+                path: vec![0],
+                property: "color".into(),
+            },
+            Patch::RemoveStyle {
+                path: vec![0],
+                property: "width".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn replaces_nodes_of_another_kind() {
+    let old = VNode::element("div", [("id", "a")], vec![VNode::text("text")]);
+    let new = VNode::element("section", [("id", "a")], vec![VNode::text("text")]);
+
+    assert_eq!(
+        check(&old, &new),
+        vec![
+            Patch::Remove {
+                parent: vec![],
+                index: 0,
+            },
+            Patch::Create {
+                parent: vec![],
+                index: 0,
+                node: new.clone(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn moves_keyed_children_as_few_times_as_possible() {
+    // The last item moved to the front is a single move
+    let patches = check(
+        &list(&["a", "b", "c", "d", "e"]),
+        &list(&["e", "a", "b", "c", "d"]),
+    );
+    assert_eq!(
+        patches,
+        vec![Patch::Move {
+            parent: vec![0],
+            from: 4,
+            to: 0,
+        }]
+    );
+
+    // Only the items outside of the longest run in order move
+    let patches = check(&list(&["a", "b", "c", "d"]), &list(&["d", "c", "b", "a"]));
+    assert_eq!(moves(&patches), 3);
+    let patches = check(
+        &list(&["a", "b", "c", "d", "e"]),
+        &list(&["b", "a", "d", "c", "e"]),
+    );
+    assert_eq!(moves(&patches), 2);
+
+    // Inserted and removed items don't move the others
+    let patches = check(
+        &list(&["a", "b", "c", "d"]),
+        &list(&["x", "a", "c", "d", "y"]),
+    );
+    assert_eq!(moves(&patches), 0);
+    assert_eq!(
+        patches,
+        vec![
+            Patch::Remove {
+                parent: vec![0],
+                index: 1,
+            },
+            Patch::Create {
+                parent: vec![0],
+                index: 3,
+                node: VNode::element("li", [("key", "y")], vec![VNode::text("y")]),
+            },
+            Patch::Create {
+                parent: vec![0],
+                index: 0,
+                node: VNode::element("li", [("key", "x")], vec![VNode::text("x")]),
+            },
+        ]
+    );
+}
+
+#[test]
+fn reconciles_arbitrary_keyed_lists() {
+    let keys = ["a", "b", "c", "d", "e", "f", "g", "h"];
+    for seed in 0..200usize {
+        // Deterministic shuffles with some items dropped and some added
+        let mut new: Vec<&str> = keys
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| (seed + index) % 5 != 0)
+            .map(|(_, key)| *key)
+            .collect();
+        let len = new.len();
+        for index in 0..len {
+            new.swap(index, (seed * 7 + index * 3) % len);
+        }
+        if seed % 3 == 0 {
+            new.insert(seed % (new.len() + 1), "new");
+        }
+
+        check(&list(&keys[..seed % keys.len()]), &list(&new));
+        check(&list(&new), &list(&keys));
+    }
+}
+
+#[test]
+fn flattens_fragments_and_components() {
+    let item = |name: &str, key: &str, text: &str| {
+        VNode::component(
+            name,
+            Some(key.into()),
+            VNode::fragment(vec![
+                VNode::element("dt", [("class", "term")], vec![VNode::text(key)]),
+                VNode::element("dd", [("class", "def")], vec![VNode::text(text)]),
+            ]),
+        )
+    };
+    let old = VNode::element(
+        "dl",
+        Vec::<(&str, &str)>::new(),
+        vec![item("Entry", "a", "first"), item("Entry", "b", "second")],
+    );
+    let new = VNode::element(
+        "dl",
+        Vec::<(&str, &str)>::new(),
+        vec![item("Entry", "b", "second"), item("Entry", "a", "updated")],
+    );
+
+    // The nodes of a keyed component move along with it
+    let patches = check(&old, &new);
+    assert_eq!(moves(&patches), 2);
+    assert!(patches.contains(&Patch::SetText {
+        path: vec![0, 3, 0],
+        text: "updated".into(),
+    }));
+
+    // A different component replaces the nodes, even if they render the same elements
+    let other = VNode::element(
+        "dl",
+        Vec::<(&str, &str)>::new(),
+        vec![item("Entry", "a", "first"), item("Other", "b", "second")],
+    );
+    let patches = check(&old, &other);
+    assert_eq!(
+        patches
+            .iter()
+            .filter(|patch| matches!(patch, Patch::Remove { .. } | Patch::Create { .. }))
+            .count(),
+        4
+    );
+}
+
+#[test]
+fn serializes_patches() {
+    let patches = diff(&list(&["a", "b"]), &list(&["b", "a"]));
+
+    assert_eq!(
+        serde_json::to_value(&patches).unwrap(),
+        json!([{ "op": "move", "parent": [0], "from": 1, "to": 0 }])
+    );
+
+    let created = diff(&VNode::fragment(vec![]), &VNode::text("Hello"));
+    assert_eq!(
+        serde_json::to_value(&created).unwrap(),
+        json!([{
+            "op": "create",
+            "parent": [],
+            "index": 0,
+            "node": { "type": "text", "text": "Hello" },
+        }])
+    );
+    let value = serde_json::to_string(&patches).unwrap();
+    assert_eq!(serde_json::from_str::<Vec<Patch>>(&value).unwrap(), patches);
+}
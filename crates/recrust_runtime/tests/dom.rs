@@ -0,0 +1,222 @@
+use std::{cell::RefCell, rc::Rc};
+
+use recrust_macro::rsx;
+use recrust_runtime::{Dom, DomNodeKind, MouseEvent, VNode, create::*, diff};
+
+fn todo_list(items: &[(&str, &str)]) -> VNode {
+    VNode::element(
+        "main",
+        [("data-testid", "app")],
+        vec![
+            VNode::element("h1", [("class", "title")], vec![VNode::text("Todos")]),
+            VNode::element(
+                "ul",
+                Vec::<(&str, &str)>::new(),
+                items
+                    .iter()
+                    .map(|(key, text)| {
+                        VNode::component(
+                            "Item",
+                            Some(key.to_string()),
+                            VNode::element(
+                                "li",
+                                [("data-testid", *key)],
+                                vec![
+                                    VNode::text(*text),
+                                    VNode::element(
+                                        "button",
+                                        [("aria-label", "Remove")],
+                                        vec![VNode::text("×")],
+                                    ),
+                                ],
+                            ),
+                        )
+                    })
+                    .collect(),
+            ),
+            VNode::fragment(vec![VNode::element(
+                "a",
+                [("href", "/about")],
+                vec![VNode::text(" About ")],
+            )]),
+        ],
+    )
+}
+
+#[test]
+fn renders_virtual_nodes() {
+    let dom = Dom::render(&todo_list(&[("1", "Milk"), ("2", "Eggs")]));
+
+    assert_eq!(
+        dom.inner_html(dom.root()),
+        "<main data-testid=\"app\"><h1 class=\"title\">Todos</h1><ul>\
+         <li data-testid=\"1\">Milk<button aria-label=\"Remove\">×</button></li>\
+         <li data-testid=\"2\">Eggs<button aria-label=\"Remove\">×</button></li>\
+         </ul><a href=\"/about\"> About </a></main>"
+    );
+
+    let list = dom.node_at(&[0, 1]).unwrap();
+    assert_eq!(dom.tag(list), Some("ul"));
+    assert_eq!(dom.node(list).parent, dom.node_at(&[0]));
+    assert_eq!(dom.node(list).children.len(), 2);
+    assert_eq!(dom.text_content(list), "Milk×Eggs×");
+    assert_eq!(
+        dom.node(dom.node_at(&[0, 1, 0, 0]).unwrap()).kind,
+        DomNodeKind::Text("Milk".into())
+    );
+}
+
+#[test]
+fn applies_patches() {
+    let old = todo_list(&[("1", "Milk"), ("2", "Eggs"), ("3", "Bread")]);
+    let new = todo_list(&[("3", "Bread"), ("1", "Oat milk"), ("4", "Tea")]);
+
+    let mut dom = Dom::render(&old);
+    let bread = dom.find_by_test_id("3").unwrap();
+    dom.apply(&diff(&old, &new));
+
+    let expected = Dom::render(&new);
+    assert_eq!(
+        dom.inner_html(dom.root()),
+        expected.inner_html(expected.root())
+    );
+    // Moved nodes are the same nodes
+    assert_eq!(dom.find_by_test_id("3"), Some(bread));
+    assert_eq!(dom.find_by_test_id("2"), None);
+}
+
+#[test]
+fn finds_elements() {
+    let dom = Dom::render(&todo_list(&[("1", "Milk"), ("2", "Eggs")]));
+
+    assert_eq!(dom.find_by_text("Todos"), dom.node_at(&[0, 0]));
+    assert_eq!(dom.find_by_text("About"), dom.node_at(&[0, 2]));
+    assert_eq!(dom.find_by_text("Eggs"), dom.node_at(&[0, 1, 1]));
+    assert_eq!(dom.find_by_text("Milk×"), None);
+
+    assert_eq!(dom.find_by_role("heading"), dom.node_at(&[0, 0]));
+    assert_eq!(dom.find_by_role("list"), dom.node_at(&[0, 1]));
+    assert_eq!(dom.find_by_role("button"), dom.node_at(&[0, 1, 0, 1]));
+    assert_eq!(dom.find_by_role("link"), dom.node_at(&[0, 2]));
+    assert_eq!(dom.find_by_role("main"), dom.node_at(&[0]));
+    assert_eq!(dom.find_by_role("dialog"), None);
+
+    assert_eq!(dom.find_by_test_id("app"), dom.node_at(&[0]));
+    assert_eq!(dom.find_by_test_id("2"), dom.node_at(&[0, 1, 1]));
+}
+
+#[test]
+fn explicit_roles_override_implicit_ones() {
+    let dom = Dom::render(&VNode::fragment(vec![
+        VNode::element("div", [("role", "button")], vec![VNode::text("Div")]),
+        VNode::element("a", Vec::<(&str, &str)>::new(), vec![]),
+        VNode::element("input", [("type", "checkbox")], vec![]),
+        VNode::element("input", Vec::<(&str, &str)>::new(), vec![]),
+    ]));
+
+    assert_eq!(dom.find_by_role("button"), dom.node_at(&[0]));
+    assert_eq!(dom.role(dom.node_at(&[1]).unwrap()), None);
+    assert_eq!(dom.find_by_role("checkbox"), dom.node_at(&[2]));
+    assert_eq!(dom.find_by_role("textbox"), dom.node_at(&[3]));
+}
+
+#[test]
+fn events_bubble_to_ancestors() {
+    let mut dom = Dom::render(&todo_list(&[("1", "Milk")]));
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let button = dom.find_by_role("button").unwrap();
+    let item = dom.find_by_test_id("1").unwrap();
+    let app = dom.find_by_test_id("app").unwrap();
+    for (id, name) in [(button, "button"), (item, "item"), (app, "app")] {
+        let log = log.clone();
+        dom.add_event_listener(id, "click", move |event| {
+            log.borrow_mut()
+                .push((name, event.target, event.current_target));
+        });
+    }
+    let other = log.clone();
+    dom.add_event_listener(app, "input", move |_| {
+        other.borrow_mut().push(("input", app, app))
+    });
+
+    let event = dom.click(button);
+    assert_eq!(
+        *log.borrow(),
+        [
+            ("button", button, button),
+            ("item", button, item),
+            ("app", button, app)
+        ]
+    );
+    assert!(!event.is_propagation_stopped());
+
+    // Clicking the item doesn't reach the button
+    log.borrow_mut().clear();
+    dom.click(item);
+    assert_eq!(*log.borrow(), [("item", item, item), ("app", item, app)]);
+}
+
+#[test]
+fn listeners_can_stop_propagation() {
+    let mut dom = Dom::render(&todo_list(&[("1", "Milk")]));
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let button = dom.find_by_role("button").unwrap();
+    let app = dom.find_by_test_id("app").unwrap();
+    for name in ["first", "second"] {
+        let log = log.clone();
+        dom.add_event_listener(button, "click", move |event| {
+            log.borrow_mut().push(name);
+            event.stop_propagation();
+        });
+    }
+    let other = log.clone();
+    dom.add_event_listener(app, "click", move |_| other.borrow_mut().push("app"));
+
+    let event = dom.click(button);
+    assert!(event.is_propagation_stopped());
+    assert_eq!(*log.borrow(), ["first", "second"]);
+}
+
+#[test]
+fn listeners_come_from_rsx() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let view = |label: &'static str| {
+        let (button_log, toolbar_log) = (log.clone(), log.clone());
+        rsx! {
+            <div role={"toolbar"} on:click={move |_: &mut MouseEvent| toolbar_log.borrow_mut().push("toolbar")}>
+                <span>
+                    <button on:click={move |event: &mut MouseEvent| {
+                        button_log.borrow_mut().push(label);
+                        if label == "stop" {
+                            event.stop_propagation();
+                        }
+                    }}>{label}</button>
+                </span>
+            </div>
+        }
+    };
+
+    let old = view("first");
+    let mut dom = Dom::render(&old);
+    let button = dom.find_by_role("button").unwrap();
+    dom.click(button);
+    assert_eq!(*log.borrow(), ["first", "toolbar"]);
+
+    // Updating binds the listeners of the new render
+    let new = view("stop");
+    dom.update(&old, &new);
+    log.borrow_mut().clear();
+    dom.click(button);
+    assert_eq!(*log.borrow(), ["stop"]);
+    assert_eq!(dom.text_content(button), "stop");
+
+    // Listeners added by hand are kept
+    let other = log.clone();
+    dom.add_event_listener(button, "click", move |_| other.borrow_mut().push("manual"));
+    dom.update(&new, &view("again"));
+    log.borrow_mut().clear();
+    dom.click(button);
+    assert_eq!(*log.borrow(), ["again", "manual", "toolbar"]);
+}
@@ -0,0 +1,308 @@
+use std::{cell::RefCell, rc::Rc};
+
+use recrust_macro::rsx;
+use recrust_runtime::{
+    Dom, Hooks, MouseEvent, VNode, create::*, render_to_string, use_effect, use_memo, use_ref,
+    use_state,
+};
+
+fn counter(props: Attributes) -> VNode {
+    let name = props.text("name").unwrap_or_else(|| "count".to_string());
+    let count = use_state(|| 0);
+    let increment = count.clone();
+
+    rsx! {
+        <div>
+            <span data-testid={name.clone()}>{count.get()}</span>
+            <button
+                data-testid={format!("{}-button", name)}
+                on:click={move |_: &mut MouseEvent| increment.update(|count| *count += 1)}
+            >
+                "+1"
+            </button>
+        </div>
+    }
+}
+
+#[test]
+fn state_is_kept_between_renders() {
+    let hooks = Hooks::new();
+    let counter = || counter(Attributes::default());
+    let old = hooks.render(counter);
+    let mut dom = Dom::render(&old);
+    assert!(!hooks.is_dirty());
+
+    // A click sets the state, which asks for a render that is diffed into the DOM
+    let button = dom.find_by_role("button").unwrap();
+    dom.click(button);
+    dom.click(button);
+    assert!(hooks.is_dirty());
+
+    let new = hooks.render(counter);
+    assert!(!hooks.is_dirty());
+    dom.update(&old, &new);
+    let label = dom.find_by_test_id("count").unwrap();
+    assert_eq!(dom.text_content(label), "2");
+
+    // The listeners of the new render are bound to the nodes updated in place
+    dom.click(button);
+    dom.update(&new, &hooks.render(counter));
+    assert_eq!(dom.text_content(label), "3");
+}
+
+#[test]
+fn refs_are_kept_without_rendering_again() {
+    let hooks = Hooks::new();
+    let render = || {
+        let renders = use_ref(|| 0);
+        *renders.borrow_mut() += 1;
+        *renders.borrow()
+    };
+
+    assert_eq!(hooks.render(render), 1);
+    assert_eq!(hooks.render(render), 2);
+    assert!(!hooks.is_dirty());
+}
+
+#[test]
+fn memos_are_computed_when_dependencies_change() {
+    let hooks = Hooks::new();
+    let computed = Rc::new(RefCell::new(Vec::new()));
+    let render = |items: Vec<i32>| {
+        let computed = computed.clone();
+        hooks.render(move || {
+            use_memo(items, |items| {
+                computed.borrow_mut().push(items.clone());
+                items.iter().sum::<i32>()
+            })
+        })
+    };
+
+    assert_eq!(render(vec![1, 2]), 3);
+    assert_eq!(render(vec![1, 2]), 3);
+    assert_eq!(render(vec![1, 2, 3]), 6);
+    assert_eq!(*computed.borrow(), [vec![1, 2], vec![1, 2, 3]]);
+}
+
+#[test]
+fn effects_run_after_render_and_clean_up() {
+    let hooks = Hooks::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let render = |id: u32| {
+        let log = log.clone();
+        hooks.render(move || {
+            let mount = log.clone();
+            use_effect((), move || mount.borrow_mut().push("mount".to_string()));
+
+            let log = log.clone();
+            use_effect(id, move || {
+                log.borrow_mut().push(format!("subscribe {}", id));
+                move || log.borrow_mut().push(format!("unsubscribe {}", id))
+            });
+        })
+    };
+
+    render(1);
+    assert!(log.borrow().is_empty());
+    hooks.run_effects();
+    assert_eq!(*log.borrow(), ["mount", "subscribe 1"]);
+
+    // Same dependencies, nothing to run
+    render(1);
+    hooks.run_effects();
+    assert_eq!(log.borrow().len(), 2);
+
+    render(2);
+    hooks.run_effects();
+    assert_eq!(log.borrow()[2..], ["unsubscribe 1", "subscribe 2"]);
+
+    // Only the last effect scheduled before running them is run
+    render(3);
+    render(4);
+    hooks.run_effects();
+    assert_eq!(log.borrow()[4..], ["unsubscribe 2", "subscribe 4"]);
+
+    hooks.unmount();
+    assert_eq!(log.borrow()[6..], ["unsubscribe 4"]);
+}
+
+#[test]
+fn components_keep_their_own_slots() {
+    let parent = Hooks::new();
+    let child = Hooks::new();
+    let render = || {
+        parent.render(|| {
+            let parent_state = use_state(|| "parent");
+            let child_state = child.render(|| use_state(|| "child").get());
+            let parent_ref = use_ref(|| 1);
+            let parent_ref = *parent_ref.borrow();
+            (parent_state.get(), child_state, parent_ref)
+        })
+    };
+
+    assert_eq!(render(), ("parent", "child", 1));
+    assert_eq!(render(), ("parent", "child", 1));
+}
+
+#[test]
+fn components_rendered_by_rsx_keep_their_state() {
+    let app = Hooks::new();
+    let render = |second: bool| {
+        app.render(|| {
+            rsx! {
+                <main>
+                    <:counter name={"first"} />
+                    {second.then(|| <:counter name={"second"} />)}
+                </main>
+            }
+        })
+    };
+    let count = |dom: &Dom, name: &str| dom.text_content(dom.find_by_test_id(name).unwrap());
+    let click = |dom: &Dom, name: &str| {
+        dom.click(dom.find_by_test_id(&format!("{}-button", name)).unwrap());
+    };
+
+    let old = render(true);
+    let mut dom = Dom::render(&old);
+    click(&dom, "first");
+    click(&dom, "first");
+    click(&dom, "second");
+    assert!(app.is_dirty());
+
+    // Each instance has its own state
+    let new = render(true);
+    assert!(!app.is_dirty());
+    dom.update(&old, &new);
+    assert_eq!(count(&dom, "first"), "2");
+    assert_eq!(count(&dom, "second"), "1");
+
+    // An instance that isn't rendered is dropped, and starts over when rendered again
+    let (old, new) = (new, render(false));
+    dom.update(&old, &new);
+    let (old, new) = (new, render(true));
+    dom.update(&old, &new);
+    assert_eq!(count(&dom, "first"), "2");
+    assert_eq!(count(&dom, "second"), "0");
+}
+
+#[test]
+fn keyed_components_follow_their_key() {
+    fn item(props: Attributes) -> VNode {
+        let id = use_state(|| props.text("key").unwrap_or_default());
+        rsx! { <li>{id.get()}</li> }
+    }
+
+    let app = Hooks::new();
+    let render = |ids: &[&str]| {
+        app.render(|| {
+            let node = rsx! {
+                <ul>{ids.iter().map(|id| <:item key={*id} />).collect::<Vec<_>>()}</ul>
+            };
+            render_to_string(&node)
+        })
+    };
+
+    assert_eq!(render(&["a", "b"]), "<ul><li>a</li><li>b</li></ul>");
+    assert_eq!(
+        render(&["c", "b", "a"]),
+        "<ul><li>c</li><li>b</li><li>a</li></ul>"
+    );
+}
+
+#[test]
+fn removed_components_are_unmounted() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let subscriber = {
+        let log = log.clone();
+        move |props: Attributes| {
+            let name = props.text("name").unwrap_or_default();
+            let log = log.clone();
+            use_effect((), move || {
+                log.borrow_mut().push(format!("subscribe {}", name));
+                move || log.borrow_mut().push(format!("unsubscribe {}", name))
+            });
+            VNode::fragment(Vec::new())
+        }
+    };
+
+    let app = Hooks::new();
+    let render = |names: &[&str]| {
+        app.render(|| {
+            // Components are taken by value, and references to closures are components too
+            let subscriber = &subscriber;
+            let children: Vec<_> = names
+                .iter()
+                .map(|name| rsx! { <:subscriber key={*name} name={*name} /> })
+                .collect();
+            rsx! { <div>{children}</div> }
+        });
+        app.run_effects();
+    };
+
+    render(&["a", "b"]);
+    assert_eq!(*log.borrow(), ["subscribe a", "subscribe b"]);
+    render(&["b"]);
+    assert_eq!(log.borrow()[2..], ["unsubscribe a"]);
+    app.unmount();
+    assert_eq!(log.borrow()[3..], ["unsubscribe b"]);
+}
+
+#[test]
+#[should_panic(expected = "`use_state` called outside of a component render")]
+fn hooks_need_a_component() {
+    use_state(|| 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "hooks called in a different order: hook 0 is `use_ref`")]
+fn hooks_are_called_in_the_same_order() {
+    let hooks = Hooks::new();
+    let render = |first: bool| {
+        hooks.render(|| {
+            if first {
+                use_state(|| 0);
+            }
+            use_ref(|| 0);
+        })
+    };
+
+    render(true);
+    render(false);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "but the previous render also called `use_ref`")]
+fn hooks_are_all_called() {
+    let hooks = Hooks::new();
+    let render = |all: bool| {
+        hooks.render(|| {
+            use_state(|| 0);
+            if all {
+                use_ref(|| 0);
+            }
+        })
+    };
+
+    render(true);
+    render(false);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "was not called by the previous render")]
+fn hooks_are_not_added() {
+    let hooks = Hooks::new();
+    let render = |all: bool| {
+        hooks.render(|| {
+            use_state(|| 0);
+            if all {
+                use_ref(|| 0);
+            }
+        })
+    };
+
+    render(false);
+    render(true);
+}
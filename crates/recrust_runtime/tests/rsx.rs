@@ -0,0 +1,195 @@
+use std::{cell::Cell, rc::Rc};
+
+use recrust_macro::{include_rsx, rsx};
+use recrust_runtime::*;
+
+fn card(props: Attributes) -> VNode {
+    rsx! {
+        <section class={"card"}>
+            <h2>{props.text("title").unwrap_or_default()}</h2>
+            {props.children()}
+        </section>
+    }
+}
+
+fn page(items: &[(u32, &str)], clicks: Rc<Cell<u32>>) -> VNode {
+    let on_click = move |_: &mut MouseEvent| clicks.set(clicks.get() + 1);
+    rsx! {
+        <main>
+            <!-- list of items -->
+            <:card title={"Items"}>
+                <ul>
+                    {items
+                        .iter()
+                        .map(|(id, name)| <li key={*id} data-testid={format!("item-{}", id)}>{*name}</li>)
+                        .collect::<Vec<_>>()}
+                </ul>
+            </:card>
+            <button disabled={items.is_empty()} on:click={on_click}>"Count: " {items.len()}</button>
+            <script>r#"if (a < b) {}"#</script>
+        </main>
+    }
+}
+
+#[test]
+fn renders_rsx_trees() {
+    let clicks = Rc::new(Cell::new(0));
+    let dom = Dom::render(&page(&[(1, "Milk"), (2, "Eggs")], clicks.clone()));
+
+    assert_eq!(
+        dom.inner_html(dom.root()),
+        "<main><!--list of items--><section class=\"card\"><h2>Items</h2><ul>\
+         <li data-testid=\"item-1\">Milk</li><li data-testid=\"item-2\">Eggs</li></ul></section>\
+         <button>Count: 2</button><script>if (a < b) {}</script></main>"
+    );
+
+    dom.click(dom.find_by_role("button").unwrap());
+    assert_eq!(clicks.get(), 1);
+}
+
+#[test]
+fn diffs_rsx_trees() {
+    let clicks = Rc::new(Cell::new(0));
+    let old = page(&[(1, "Milk"), (2, "Eggs")], clicks.clone());
+    let new = page(&[(2, "Eggs"), (3, "Tea"), (4, "Jam")], clicks.clone());
+
+    // The items are keyed by id, inside of the component
+    let patches = diff(&old, &new);
+    assert!(patches.contains(&Patch::Remove {
+        parent: vec![0, 1, 1],
+        index: 0,
+    }));
+    assert!(patches.contains(&Patch::SetText {
+        path: vec![0, 2, 1],
+        text: "3".into(),
+    }));
+
+    let mut dom = Dom::render(&old);
+    let eggs = dom.find_by_test_id("item-2").unwrap();
+    dom.update(&old, &new);
+    let expected = Dom::render(&new);
+    assert_eq!(
+        dom.inner_html(dom.root()),
+        expected.inner_html(expected.root())
+    );
+    assert_eq!(dom.find_by_test_id("item-2"), Some(eggs));
+
+    // The component is replaced when it changes, and so is a tag
+    let VNode::Element(main) = &new else {
+        panic!("expected an element");
+    };
+    assert!(matches!(
+        &main.children[..],
+        [VNode::Fragment { children }] if matches!(&children[1], VNode::Component(card) if card.name == "card")
+    ));
+}
+
+#[test]
+fn boolean_attributes() {
+    let input = rsx! { <input aria-label={"Count"} disabled={true} required={false} value={3} /> };
+    assert_eq!(
+        Dom::render(&input).inner_html(Dom::new().root()),
+        "<input aria-label=\"Count\" disabled=\"\" value=\"3\">"
+    );
+}
+
+#[test]
+fn inner_html() {
+    let article = |html: &str| rsx! { <article inner_html={Trusted::new(html)} /> };
+    let old = article("<b>bold</b>");
+    let new = article("<i>italic</i> & co");
+
+    let mut dom = Dom::render(&old);
+    assert_eq!(dom.inner_html(dom.root()), "<article><b>bold</b></article>");
+
+    // The HTML is raw text, so it is updated in place
+    assert_eq!(
+        diff(&old, &new),
+        [Patch::SetText {
+            path: vec![0, 0],
+            text: "<i>italic</i> & co".into(),
+        }]
+    );
+    dom.update(&old, &new);
+    assert_eq!(
+        dom.inner_html(dom.root()),
+        "<article><i>italic</i> & co</article>"
+    );
+}
+
+#[test]
+fn class_merging() {
+    let extra = vec![("class", "btn card"), ("id", "save")];
+    let node = rsx! {
+        <div class={"btn  big"} class={["", "wide"]} class:active={true} class:hidden={false} {..extra} />
+    };
+    assert_eq!(
+        render_to_string(&node),
+        r#"<div class="btn big wide active card" id="save"></div>"#
+    );
+
+    // A class attribute without classes is left out
+    let node = rsx! { <div class={[""]} class:active={false} /> };
+    assert_eq!(render_to_string(&node), "<div></div>");
+
+    assert_eq!(
+        VNode::element("p", [("class", "a b"), ("class", "b c")], Vec::new()),
+        VNode::element("p", [("class", "a b c")], Vec::new())
+    );
+}
+
+#[test]
+fn style_directives() {
+    let font = "\"Fira Sans\", serif";
+    let node = rsx! {
+        <p
+            style={"color: red; background: url(\"a;b.png\"); width: 1px"}
+            style:width={20}
+            style:opacity={0.5}
+            style:margin={0}
+            style:font-family={font}
+            style:display={false}
+        />
+    };
+    assert_eq!(
+        render_to_string(&node),
+        "<p style=\"color: red; background: url(&quot;a;b.png&quot;); width: 20px; \
+         opacity: 0.5; margin: 0; font-family: &quot;Fira Sans&quot;, serif\"></p>"
+    );
+
+    // Changed properties are patched one by one
+    let sized = |width: u32, color: &str| rsx! { <div style:width={width} style:color={color} /> };
+    let (old, new) = (sized(20, "red"), sized(30, ""));
+    assert_eq!(
+        diff(&old, &new),
+        [
+            Patch::RemoveStyle {
+                path: vec![0],
+                property: "color".into(),
+            },
+            Patch::SetStyle {
+                path: vec![0],
+                property: "width".into(),
+                value: "30px".into(),
+            },
+        ]
+    );
+    let mut dom = Dom::render(&old);
+    dom.update(&old, &new);
+    assert_eq!(
+        dom.inner_html(dom.root()),
+        "<div style=\"width: 30px\"></div>"
+    );
+}
+
+#[test]
+fn external_templates() {
+    let title = "Items";
+    let items = ["Milk", "Eggs"];
+    let node = include_rsx!("tests/templates/list.rsx");
+    assert_eq!(
+        render_to_string(&node),
+        "<section class=\"card\"><!--rendered from an external template--><h2>Items</h2>\
+         <ul><li>Milk</li><li>Eggs</li></ul></section>"
+    );
+}
@@ -0,0 +1,67 @@
+use recrust_macro::rsx;
+use recrust_runtime::*;
+
+#[test]
+fn renders_elements_and_texts() {
+    let node = rsx! {
+        <p title={"a \"quote\""}>"1 < 2 & 3"<br>"second line"</p>
+    };
+    assert_eq!(
+        render_to_string(&node),
+        r#"<p title="a &quot;quote&quot;">1 &lt; 2 &amp; 3<br>second line</p>"#
+    );
+}
+
+#[test]
+fn renders_comments() {
+    let node = rsx! {
+        <div>
+            <!-- "hydration marker" -->
+            <!-- TODO: add the footer -->
+            <span />
+        </div>
+    };
+    assert_eq!(
+        render_to_string(&node),
+        "<div><!--hydration marker--><!--TODO: add the footer--><span></span></div>"
+    );
+
+    // A comment cannot be closed by its own text
+    let comment = |text| render_to_string(&VNode::comment(text));
+    assert_eq!(comment("a --> b"), "<!--a - - > b-->");
+    assert_eq!(comment("--->"), "<!--- - - >-->");
+    assert_eq!(comment(">x"), "<!-- >x-->");
+    assert_eq!(comment("->x"), "<!--- >x-->");
+    assert_eq!(comment("a-"), "<!--a- -->");
+}
+
+#[test]
+fn renders_raw_text_unescaped() {
+    let node = rsx! {
+        <div>
+            <style>r#"p > a { color: red; }"#</style>
+            <pre class={"code"} raw>"<b>bold</b> & co"</pre>
+            <script>r#"if (a < b && c) {}"#</script>
+        </div>
+    };
+    assert_eq!(
+        render_to_string(&node),
+        concat!(
+            "<div>",
+            "<style>p > a { color: red; }</style>",
+            r#"<pre class="code"><b>bold</b> & co</pre>"#,
+            "<script>if (a < b && c) {}</script>",
+            "</div>"
+        )
+    );
+}
+
+#[test]
+fn renders_inner_html_unescaped() {
+    let html = Trusted::new("<b>bold</b> & co");
+    let node = rsx! { <div class={"post"} inner_html={html} /> };
+    assert_eq!(
+        render_to_string(&node),
+        r#"<div class="post"><b>bold</b> & co</div>"#
+    );
+}
@@ -0,0 +1,136 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use recrust_ast::{FormatOptions, Level, format_source, line_column};
+
+const USAGE: &str = "\
+Formats the `rsx!` blocks in Rust source files
+
+Usage: rsxfmt [OPTIONS] [PATH]...
+
+Paths can be files or directories, which are searched for `.rs` files (default: `.`).
+
+Options:
+      --check          Only report files that would be reformatted, and fail if there are any
+      --width <WIDTH>  Maximum line width [default: 100]
+      --indent <N>     Number of spaces per indentation level [default: 4]
+  -h, --help           Print help";
+
+fn main() -> ExitCode {
+    let mut check = false;
+    let mut options = FormatOptions::default();
+    let mut paths = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--width" | "--indent" => {
+                let Some(value) = args.next().and_then(|value| value.parse::<usize>().ok()) else {
+                    eprintln!("error: `{}` expects a number\n\n{}", arg, USAGE);
+                    return ExitCode::from(2);
+                };
+                if arg == "--width" {
+                    options.max_width = value;
+                } else {
+                    options.indent = " ".repeat(value);
+                }
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ if arg.starts_with('-') => {
+                eprintln!("error: unknown option `{}`\n\n{}", arg, USAGE);
+                return ExitCode::from(2);
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        paths.push(PathBuf::from("."));
+    }
+
+    let mut files = Vec::new();
+    for path in &paths {
+        if let Err(err) = collect_files(path, &mut files) {
+            eprintln!("error: {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let mut failed = false;
+    for file in files {
+        let source = match fs::read_to_string(&file) {
+            Ok(source) => source,
+            Err(err) => {
+                eprintln!("error: {}: {}", file.display(), err);
+                failed = true;
+                continue;
+            }
+        };
+
+        let formatted = format_source(&source, &options);
+        for diagnostic in &formatted.diagnostics {
+            let (line, column) = line_column(diagnostic.span);
+            let level = match diagnostic.level {
+                Level::Warning => "warning",
+                Level::Error => "error",
+            };
+            eprintln!(
+                "{}:{}:{}: {}: {}",
+                file.display(),
+                line,
+                column,
+                level,
+                diagnostic.message
+            );
+            failed |= diagnostic.level == Level::Error;
+        }
+
+        if formatted.output == source {
+            continue;
+        }
+        if check {
+            println!("would reformat {}", file.display());
+            failed = true;
+        } else if let Err(err) = fs::write(&file, formatted.output) {
+            eprintln!("error: {}: {}", file.display(), err);
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Collects `.rs` files, skipping hidden directories and `target`.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for entry in entries {
+        let name = entry.file_name().unwrap_or_default().to_string_lossy();
+        if entry.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                collect_files(&entry, files)?;
+            }
+        } else if name.ends_with(".rs") {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}